@@ -1,19 +1,24 @@
 use nagara_core_runtime::opaque::SessionKeys;
 use nagara_core_runtime::{
     AccountId, AuraConfig, BalancesConfig, BigBrotherCouncilConfig, ContractsConfig, GrandpaConfig,
-    RuntimeGenesisConfig, SessionConfig, Signature, SudoConfig, SystemConfig, ValidatorSetConfig,
-    WASM_BINARY,
+    PDAFilesConfig, RuntimeGenesisConfig, ServicerRegistryConfig, SessionConfig, Signature,
+    SudoConfig, SystemConfig, ValidatorSetConfig, WASM_BINARY,
 };
+use nagara_pda_files::FileInformationArgs;
+use nagara_registry_servicers::RemoteAttestationDeviceSupplyArgs;
 use sc_service::ChainType;
 use sc_telemetry::TelemetryEndpoints;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{ed25519, sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 use ss58_registry::{Ss58AddressFormatRegistry, Token, TokenRegistry};
 
 // The URL for the telemetry server.
 const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+const GENESIS_FILE_CHUNK_SIZE: u32 = 1024;
+const GENESIS_FILE_SEED: &[u8] = b"nagara genesis file\n";
+const GENESIS_FILE_SIZE: usize = 1024;
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate
 /// ChainSpec type.
@@ -132,6 +137,37 @@ pub fn testnet_config() -> Result<ChainSpec, String> {
     ))
 }
 
+/// Generate a genesis attester from seed, supplied by the given big brother.
+fn genesis_attester(
+    big_brother: &AccountId,
+    seed: &str,
+    serial_number: u32,
+) -> (AccountId, RemoteAttestationDeviceSupplyArgs) {
+    let id = get_from_seed::<ed25519::Public>(seed);
+    let mut guid = [0u8; 16];
+    guid.copy_from_slice(&id.0[..16]);
+
+    (
+        big_brother.clone(),
+        RemoteAttestationDeviceSupplyArgs {
+            id,
+            guid,
+            serial_number,
+        },
+    )
+}
+
+/// Content of the genesis file, the seed repeated up to the file size,
+/// servicers can rebuild it to answer storage challenges.
+pub fn genesis_file_content() -> Vec<u8> {
+    GENESIS_FILE_SEED
+        .iter()
+        .copied()
+        .cycle()
+        .take(GENESIS_FILE_SIZE)
+        .collect()
+}
+
 /// Session keys
 fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
     SessionKeys {
@@ -160,6 +196,8 @@ fn testnet_genesis(
     endowed_accounts: Vec<AccountId>,
     _enable_println: bool,
 ) -> RuntimeGenesisConfig {
+    let genesis_file = genesis_file_content();
+
     RuntimeGenesisConfig {
         system: SystemConfig {
             code: wasm_binary.to_vec(),
@@ -206,8 +244,43 @@ fn testnet_genesis(
             contract_master: Some(root_key.clone()),
         },
         big_brother_council: BigBrotherCouncilConfig {
-            elder: Some(root_key),
+            elder: Some(root_key.clone()),
             ..Default::default()
         },
+        servicer_registry: ServicerRegistryConfig {
+            attesters: vec![
+                genesis_attester(&root_key, "Alice//attester", 1),
+                genesis_attester(&root_key, "Bob//attester", 2),
+            ],
+            mediators: vec![], // legality is judged after genesis, added by the council
+            servicers: vec![(
+                get_account_id_from_seed::<sr25519::Public>("Bob"),
+                vec![(
+                    get_from_seed::<ed25519::Public>("Bob//attester"),
+                    get_from_seed::<ed25519::Public>("Bob//peer"),
+                )],
+            )],
+        },
+        pda_files: PDAFilesConfig {
+            files: vec![FileInformationArgs {
+                hash: sp_core::blake2_256(&genesis_file),
+                uploader: root_key.clone(),
+                big_brother: root_key,
+                replication_factor: 1,
+                replicas: vec![(
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                    get_from_seed::<ed25519::Public>("Bob//attester"),
+                )],
+                royalties: vec![], // uploader & big brother
+                transfer_fee: nagara_core_runtime::constants::TOKEN,
+                download_fee: None,
+                size: genesis_file.len() as u64,
+                chunk_size: GENESIS_FILE_CHUNK_SIZE,
+                chunk_root: nagara_pda_files::ngr_chmk::root_of(
+                    &genesis_file,
+                    GENESIS_FILE_CHUNK_SIZE,
+                ),
+            }],
+        },
     }
 }
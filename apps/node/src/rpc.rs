@@ -7,12 +7,19 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
-use nagara_core_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+    RpcModule,
+};
+use nagara_core_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, Nonce};
+use nagara_pda_files::runtime_api::PdaFilesApi as PdaFilesRuntimeApi;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::traits::Block as BlockT;
 
 pub use sc_rpc_api::DenyUnsafe;
 
@@ -26,6 +33,168 @@ pub struct FullDeps<C, P> {
     pub deny_unsafe: DenyUnsafe,
 }
 
+/// File summary as returned by the `pda_*` RPC methods.
+pub type FileSummary = nagara_pda_files::FileSummary<AccountId, AssetId, Balance, BlockNumber>;
+
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+/// PDA files RPC methods.
+#[rpc(server, namespace = "pda")]
+pub trait PdaFilesApi<BlockHash> {
+    /// File by its account.
+    #[method(name = "file")]
+    fn file(&self, file: AccountId, at: Option<BlockHash>) -> RpcResult<Option<FileSummary>>;
+
+    /// File account derived from a content hash & uploader.
+    #[method(name = "fileAccountOf")]
+    fn file_account_of(
+        &self,
+        hash: nagara_pda_files::FileHash,
+        uploader: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AccountId>;
+
+    /// File (account & summary) by its content hash.
+    #[method(name = "fileByHash")]
+    fn file_by_hash(
+        &self,
+        hash: nagara_pda_files::FileHash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<(AccountId, FileSummary)>>;
+
+    /// Files owned by an account.
+    #[method(name = "filesOfOwner")]
+    fn files_of_owner(&self, owner: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+
+    /// Upload, minimum download, storage (per period) & minimum transfer fees
+    /// of a file size, in the native currency or in a pricing asset.
+    #[method(name = "quote")]
+    fn quote(
+        &self,
+        size: u64,
+        pricing_asset: Option<AssetId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<nagara_pda_files::FeeQuote<Balance>>>;
+
+    /// Current download, transfer, minimum bid & storage fees of a file.
+    #[method(name = "fileQuote")]
+    fn file_quote(
+        &self,
+        file: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<nagara_pda_files::FileQuote<Balance>>>;
+}
+
+/// Implementation of the `pda_*` RPC methods.
+pub struct PdaFiles<C> {
+    client: Arc<C>,
+}
+
+impl<C> PdaFiles<C> {
+    /// Create new `PdaFiles` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+        }
+    }
+}
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query PDA files",
+        Some(error.to_string()),
+    ))
+    .into()
+}
+
+impl<C> PdaFilesApiServer<<Block as BlockT>::Hash> for PdaFiles<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: PdaFilesRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber>,
+{
+    fn file(
+        &self,
+        file: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<FileSummary>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .file(at, file)
+            .map_err(runtime_error)
+    }
+
+    fn file_account_of(
+        &self,
+        hash: nagara_pda_files::FileHash,
+        uploader: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AccountId> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .file_account_of(at, hash, uploader)
+            .map_err(runtime_error)
+    }
+
+    fn file_by_hash(
+        &self,
+        hash: nagara_pda_files::FileHash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<(AccountId, FileSummary)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .file_by_hash(at, hash)
+            .map_err(runtime_error)
+    }
+
+    fn files_of_owner(
+        &self,
+        owner: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AccountId>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .files_of_owner(at, owner)
+            .map_err(runtime_error)
+    }
+
+    fn quote(
+        &self,
+        size: u64,
+        pricing_asset: Option<AssetId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<nagara_pda_files::FeeQuote<Balance>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .quote(at, size, pricing_asset)
+            .map_err(runtime_error)
+    }
+
+    fn file_quote(
+        &self,
+        file: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<nagara_pda_files::FileQuote<Balance>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .file_quote(at, file)
+            .map_err(runtime_error)
+    }
+}
+
 /// Instantiate all full RPC extensions.
 pub fn create_full<C, P>(
     deps: FullDeps<C, P>,
@@ -37,6 +206,7 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
+    C::Api: PdaFilesRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber>,
     P: TransactionPool + 'static, {
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -49,7 +219,8 @@ where
     } = deps;
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(PdaFiles::new(client).into_rpc())?;
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
@@ -0,0 +1,298 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Chunk Merkle manifest shared by clients, servicers and the runtime.
+//!
+//! A file is split into fixed size chunks (the last one may be shorter), every
+//! chunk is hashed into a leaf with `blake2_256(LEAF_PREFIX ++ chunk)`, parents
+//! are `blake2_256(NODE_PREFIX ++ left ++ right)` and an odd node out is
+//! promoted to the next level as is. The root of that tree is the file chunk
+//! root. The prefixes keep a 64 bytes chunk from passing as an inner node.
+
+use sp_std::vec::Vec;
+
+pub type ChunkHash = [u8; 32];
+
+/// Domain tag prepended to a chunk when hashing a leaf
+pub const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain tag prepended to the children when hashing an inner node
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Number of chunks of a file, an empty file still has one (empty) chunk
+pub fn chunk_count(size: u64, chunk_size: u32) -> u32 {
+    let chunks = size.div_ceil(chunk_size.max(1) as u64).max(1);
+
+    u32::try_from(chunks).unwrap_or(u32::MAX)
+}
+
+/// Expected length of a chunk, zero if out of range
+pub fn chunk_length(size: u64, chunk_size: u32, index: u32) -> u64 {
+    let offset = (index as u64).saturating_mul(chunk_size as u64);
+
+    size.saturating_sub(offset).min(chunk_size as u64)
+}
+
+/// Number of siblings in a proof for a tree of `leaf_count` leaves
+pub fn proof_depth(leaf_count: u32) -> u32 {
+    leaf_count.max(1).next_power_of_two().trailing_zeros()
+}
+
+pub fn leaf_hash(chunk: &[u8]) -> ChunkHash {
+    let mut preimage = Vec::with_capacity(chunk.len().saturating_add(1));
+    preimage.push(LEAF_PREFIX);
+    preimage.extend_from_slice(chunk);
+
+    sp_core::hashing::blake2_256(&preimage)
+}
+
+fn node_hash(left: &ChunkHash, right: &ChunkHash) -> ChunkHash {
+    let mut preimage = [0u8; 65];
+    preimage[0] = NODE_PREFIX;
+    preimage[1..33].copy_from_slice(left);
+    preimage[33..].copy_from_slice(right);
+
+    sp_core::hashing::blake2_256(&preimage)
+}
+
+fn next_level(level: &[ChunkHash]) -> Vec<ChunkHash> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            match pair {
+                | [left, right] => node_hash(left, right),
+                | [single] => *single,
+                | _ => unreachable!("chunks(2) yields one or two nodes"),
+            }
+        })
+        .collect()
+}
+
+/// Chunk root over the leaves
+pub fn root(leaves: &[ChunkHash]) -> ChunkHash {
+    if leaves.is_empty() {
+        return leaf_hash(&[]);
+    }
+
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+
+    level[0]
+}
+
+/// Chunk root of a file content split by `chunk_size`
+pub fn root_of(content: &[u8], chunk_size: u32) -> ChunkHash {
+    root(&leaves_of(content, chunk_size))
+}
+
+/// Leaves of a file content split by `chunk_size`
+pub fn leaves_of(content: &[u8], chunk_size: u32) -> Vec<ChunkHash> {
+    if content.is_empty() {
+        return sp_std::vec![leaf_hash(&[])];
+    }
+
+    content
+        .chunks(chunk_size.max(1) as usize)
+        .map(leaf_hash)
+        .collect()
+}
+
+/// Inclusion proof (bottom-up siblings) of the leaf at `index`
+pub fn proof(leaves: &[ChunkHash], index: u32) -> Option<Vec<ChunkHash>> {
+    let mut index = index as usize;
+
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+
+        if sibling < level.len() {
+            siblings.push(level[sibling]);
+        }
+
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    Some(siblings)
+}
+
+/// Verify an inclusion proof of `leaf` at `index` against the chunk root
+pub fn verify_proof(
+    root: &ChunkHash,
+    leaf_count: u32,
+    index: u32,
+    leaf: ChunkHash,
+    proof: &[ChunkHash],
+) -> bool {
+    if index >= leaf_count {
+        return false;
+    }
+
+    let mut hash = leaf;
+    let mut index = index;
+    let mut width = leaf_count;
+    let mut siblings = proof.iter();
+
+    while width > 1 {
+        if index % 2 == 1 {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            hash = node_hash(sibling, &hash);
+        } else if index + 1 < width {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            hash = node_hash(&hash, sibling);
+        }
+
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+
+    siblings.next().is_none() && hash.eq(root)
+}
+
+/// Verify a chunk (content & length) of a file against its chunk root
+pub fn verify_chunk(
+    root: &ChunkHash,
+    size: u64,
+    chunk_size: u32,
+    index: u32,
+    chunk: &[u8],
+    proof: &[ChunkHash],
+) -> bool {
+    let leaf_count = chunk_count(size, chunk_size);
+
+    index < leaf_count
+        && chunk.len() as u64 == chunk_length(size, chunk_size, index)
+        && verify_proof(root, leaf_count, index, leaf_hash(chunk), proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHUNK_SIZE: u32 = 4;
+
+    fn content(chunks: u32) -> Vec<u8> {
+        (0..chunks.saturating_mul(CHUNK_SIZE))
+            .map(|byte| byte as u8)
+            .collect()
+    }
+
+    fn chunk(content: &[u8], index: u32) -> &[u8] {
+        content
+            .chunks(CHUNK_SIZE as usize)
+            .nth(index as usize)
+            .unwrap()
+    }
+
+    fn proof_of(content: &[u8], index: u32) -> Vec<ChunkHash> {
+        proof(&leaves_of(content, CHUNK_SIZE), index).unwrap()
+    }
+
+    fn verify(content: &[u8], index: u32, chunk: &[u8], proof: &[ChunkHash]) -> bool {
+        let root = root_of(content, CHUNK_SIZE);
+
+        verify_chunk(&root, content.len() as u64, CHUNK_SIZE, index, chunk, proof)
+    }
+
+    fn verify_all(content: &[u8]) {
+        let leaf_count = chunk_count(content.len() as u64, CHUNK_SIZE);
+
+        for index in 0..leaf_count {
+            let proof = proof_of(content, index);
+
+            assert!(proof.len() as u32 <= proof_depth(leaf_count));
+            assert!(verify(content, index, chunk(content, index), &proof));
+        }
+    }
+
+    #[test]
+    fn valid_proofs_verify() {
+        verify_all(&content(8));
+    }
+
+    #[test]
+    fn odd_leaf_counts_verify() {
+        for chunks in [1, 3, 5, 7, 9] {
+            verify_all(&content(chunks));
+        }
+
+        // shorter last chunk
+        let mut content = content(5);
+        content.truncate(content.len() - 1);
+        verify_all(&content);
+    }
+
+    #[test]
+    fn single_chunk_root_is_its_leaf() {
+        let content = content(1);
+
+        assert_eq!(root_of(&content, CHUNK_SIZE), leaf_hash(&content));
+        assert!(proof_of(&content, 0).is_empty());
+    }
+
+    #[test]
+    fn wrong_index_fails() {
+        let content = content(5);
+        let proof = proof_of(&content, 0);
+
+        assert!(verify(&content, 0, chunk(&content, 0), &proof));
+        for index in 1..6 {
+            assert!(!verify(&content, index, chunk(&content, 0), &proof));
+        }
+        assert_eq!(super::proof(&leaves_of(&content, CHUNK_SIZE), 5), None);
+    }
+
+    #[test]
+    fn tampered_sibling_fails() {
+        let content = content(6);
+        let proof = proof_of(&content, 2);
+
+        for sibling in 0..proof.len() {
+            let mut tampered = proof.clone();
+            tampered[sibling][0] ^= 1;
+
+            assert!(!verify(&content, 2, chunk(&content, 2), &tampered));
+        }
+
+        // missing & extra siblings
+        let mut extended = proof.clone();
+        extended.push(root_of(&content, CHUNK_SIZE));
+        assert!(!verify(&content, 2, chunk(&content, 2), &proof[1..]));
+        assert!(!verify(&content, 2, chunk(&content, 2), &extended));
+    }
+
+    #[test]
+    fn tampered_chunk_fails() {
+        let content = content(3);
+        let proof = proof_of(&content, 1);
+        let mut tampered = chunk(&content, 1).to_vec();
+        tampered[0] ^= 1;
+
+        assert!(!verify(&content, 1, &tampered, &proof));
+        assert!(!verify(&content, 1, &tampered[..3], &proof));
+    }
+
+    #[test]
+    fn inner_node_is_not_a_leaf() {
+        // the two children of a node, given as a single 64 bytes chunk
+        let leaves = leaves_of(&content(2), CHUNK_SIZE);
+        let root = root(&leaves);
+        let forged = [leaves[0], leaves[1]].concat();
+
+        assert_eq!(root, node_hash(&leaves[0], &leaves[1]));
+        assert_ne!(leaf_hash(&forged), root);
+        assert!(!verify_chunk(&root, 64, 64, 0, &forged, &[]));
+    }
+}
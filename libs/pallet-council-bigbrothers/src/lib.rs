@@ -335,19 +335,24 @@ pub mod pallet {
             }
         }
 
+        /// check whether an account is a Council Member (elder included)
+        pub fn is_council_member(who: &T::AccountId) -> bool {
+            if let Some(elder) = Self::elder() {
+                if elder.eq(who) {
+                    return true; // elder is also a member
+                }
+            }
+
+            <Members<T>>::get().contains(who)
+        }
+
         /// ensure origin is Council Member and return it's account
         pub fn ensure_and_get_council_member(
             origin: OriginFor<T>,
         ) -> Result<T::AccountId, sp_runtime::DispatchError> {
             let signed_caller = ensure_signed(origin)?;
 
-            if let Some(elder) = Self::elder() {
-                if elder.eq(&signed_caller) {
-                    return Ok(signed_caller); // elder is also a member
-                }
-            }
-
-            if <Members<T>>::get().contains(&signed_caller) {
+            if Self::is_council_member(&signed_caller) {
                 Ok(signed_caller)
             } else {
                 Err(<Error<T>>::CouncilMemberOnly.into())
@@ -1,19 +1,50 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use nagara_chunk_merkle as ngr_chmk;
 pub use nagara_council_bigbrothers as ngr_bbcm;
 pub use nagara_registry_servicers as ngr_svrg;
 pub use pallet::*;
 
+pub mod migrations;
+pub mod runtime_api;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 pub type AccountTypeOf<T> = <T as frame_system::Config>::AccountId;
+pub type AssetCreditOf<T> =
+    frame_support::traits::fungibles::Credit<AccountTypeOf<T>, <T as Config>::Assets>;
+pub type AssetIdOf<T> =
+    <<T as Config>::Assets as frame_support::traits::fungibles::Inspect<AccountTypeOf<T>>>::AssetId;
 pub type AttesterId = sp_core::ed25519::Public;
 pub type BalanceCurrencyTypeOf<T> =
     <<T as Config>::Currency as frame_support::traits::Currency<AccountTypeOf<T>>>::Balance;
 pub type BlockNumber<T> = frame_system::pallet_prelude::BlockNumberFor<T>;
-pub type FileHash = [u8; 32];
+pub type NegativeImbalanceTypeOf<T> = <<T as Config>::Currency as frame_support::traits::Currency<
+    AccountTypeOf<T>,
+>>::NegativeImbalance;
+pub type FileHash = ngr_chmk::ChunkHash;
+pub type FileSummaryOf<T> =
+    FileSummary<AccountTypeOf<T>, AssetIdOf<T>, BalanceCurrencyTypeOf<T>, BlockNumber<T>>;
 pub type UniqueMap<K, V> = sp_std::collections::btree_map::BTreeMap<K, V>;
 
 pub const PALLET_IDENTIFICATION: frame_support::PalletId = frame_support::PalletId(*b"ngr/pdaf");
 
+/// File account of a content hash & uploader, a sub account of the pallet over
+/// their blake2 hash as the raw pair does not fit in the account
+pub fn derive_file_account<AccountId>(hash: &FileHash, uploader: &impl codec::Encode) -> AccountId
+where
+    AccountId: codec::Decode + codec::Encode, {
+    let seed = sp_core::hashing::blake2_256(&codec::Encode::encode(&(hash, uploader)));
+
+    sp_runtime::traits::AccountIdConversion::into_sub_account_truncating(
+        &PALLET_IDENTIFICATION,
+        seed,
+    )
+}
+
 pub trait FeeFromBytes {
     /// The type that is returned as result from calculation.
     type Balance: sp_arithmetic::traits::BaseArithmetic
@@ -35,7 +66,10 @@ pub mod pallet {
 
     // region: Pallet Declaration
 
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -46,7 +80,8 @@ pub mod pallet {
         /// Currency for pallet operations
         type Currency: frame_support::traits::Currency<Self::AccountId>
             + frame_support::traits::fungible::Mutate<Self::AccountId>
-            + frame_support::traits::fungible::Inspect<Self::AccountId>;
+            + frame_support::traits::fungible::Inspect<Self::AccountId>
+            + frame_support::traits::ReservableCurrency<Self::AccountId>;
         /// Upload fee per byte
         type UploadFeePerByte: FeeFromBytes<Balance = BalanceCurrencyTypeOf<Self>>;
         /// Download fee per byte
@@ -56,15 +91,82 @@ pub mod pallet {
         /// Storage period
         #[pallet::constant]
         type StoragePeriod: sp_core::Get<BlockNumber<Self>>;
+        /// Blocks an underfunded (delinquent) file is kept before deletion
+        #[pallet::constant]
+        type GracePeriod: sp_core::Get<BlockNumber<Self>>;
+        /// Portion of the file account balance paid to the servicer when the
+        /// owner deletes the file
+        #[pallet::constant]
+        type EarlyTerminationFee: sp_core::Get<sp_runtime::Percent>;
         /// Servicer portion of upload fee
         #[pallet::constant]
         type ServicerUploadFeeDistribution: sp_core::Get<sp_runtime::Percent>;
         /// Big brother download fee distribution
         #[pallet::constant]
         type BigBrotherDownloadFeeDistribution: sp_core::Get<sp_runtime::Percent>;
-        /// Initial owner + big brother transfer fee distribution
+        /// Royalty portion of sale & download proceeds, split by the file
+        /// royalty table
         #[pallet::constant]
         type RoyaltyFeeDistribution: sp_core::Get<sp_runtime::Percent>;
+        /// Maximum royalty beneficiaries of a file
+        #[pallet::constant]
+        type MaxRoyaltyBeneficiaries: sp_core::Get<u32>;
+        /// Handler of fee remainders left after distribution (e.g. rounding)
+        type OnFeeRemainder: frame_support::traits::OnUnbalanced<NegativeImbalanceTypeOf<Self>>;
+        /// Assets a file may be priced in instead of the native currency
+        type Assets: frame_support::traits::fungibles::Inspect<
+                Self::AccountId,
+                Balance = BalanceCurrencyTypeOf<Self>,
+            > + frame_support::traits::fungibles::Mutate<Self::AccountId>
+            + frame_support::traits::fungibles::Balanced<Self::AccountId>;
+        /// Handler of asset fee remainders left after distribution
+        type OnAssetFeeRemainder: frame_support::traits::OnUnbalanced<AssetCreditOf<Self>>;
+        /// Blocks a download license stays valid, re-downloads within are free
+        #[pallet::constant]
+        type LicenseDuration: sp_core::Get<BlockNumber<Self>>;
+        /// Maximum access list entries (grants or denials) per file
+        #[pallet::constant]
+        type MaxAccessListEntries: sp_core::Get<u32>;
+        /// Randomness source for storage challenges
+        type Randomness: frame_support::traits::Randomness<Self::Hash, BlockNumber<Self>>;
+        /// Blocks a servicer has to answer a storage challenge
+        #[pallet::constant]
+        type ChallengeResponsePeriod: sp_core::Get<BlockNumber<Self>>;
+        /// Maximum chunk size of a file
+        #[pallet::constant]
+        type MaxChunkSize: sp_core::Get<u32>;
+        /// Maximum depth of a chunk inclusion proof
+        #[pallet::constant]
+        type MaxProofDepth: sp_core::Get<u32>;
+        /// Maximum replicas (servicer copies) of a file
+        #[pallet::constant]
+        type MaxReplicas: sp_core::Get<u32>;
+        /// Maximum files charged for storage fee automatically per block
+        #[pallet::constant]
+        type MaxStorageFeeChargesPerBlock: sp_core::Get<u32>;
+        /// Maximum existing files migrated or indexed by owner & uploader per
+        /// block
+        #[pallet::constant]
+        type MaxFileIndexBackfillsPerBlock: sp_core::Get<u32>;
+        /// Maximum replicas of revoked attesters flagged per block
+        #[pallet::constant]
+        type MaxReplicaRevocationsPerBlock: sp_core::Get<u32>;
+        /// Maximum access list entries & licenses of deleted files removed per
+        /// block
+        #[pallet::constant]
+        type MaxFileEntryRemovalsPerBlock: sp_core::Get<u32>;
+        /// Maximum pending (escrowed) offers per file
+        #[pallet::constant]
+        type MaxOffersPerFile: sp_core::Get<u32>;
+        /// Maximum blocks an auction may run
+        #[pallet::constant]
+        type MaxAuctionDuration: sp_core::Get<BlockNumber<Self>>;
+        /// Blocks before an owner pricing update takes effect
+        #[pallet::constant]
+        type PricingNoticePeriod: sp_core::Get<BlockNumber<Self>>;
+        /// Maximum files an account may own, and may have uploaded (kept)
+        #[pallet::constant]
+        type MaxFilesPerAccount: sp_core::Get<u32>;
     }
 
     // endregion
@@ -73,57 +175,477 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn files)]
-    pub(super) type Files<T: Config> = StorageMap<
+    pub(super) type Files<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, FileInformation<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn hashes)]
+    pub(super) type Hashes<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, FileHash, T::AccountId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn access_modes)]
+    pub(super) type AccessModes<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, AccessMode>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn access_lists)]
+    pub(super) type AccessLists<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId, // file
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        Option<BlockNumber<T>>, // expires at, None means never
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn access_list_sizes)]
+    pub(super) type AccessListSizes<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn licenses)]
+    pub(super) type Licenses<T: Config> = StorageDoubleMap<
         _,
         frame_support::Blake2_128Concat,
+        T::AccountId, // file
+        frame_support::Blake2_128Concat,
         T::AccountId,
-        FileInformation<T::AccountId, BalanceCurrencyTypeOf<T>>,
+        License<BlockNumber<T>>,
     >;
 
+    /// deleted files whose access list & licenses are still being removed
+    /// (on idle)
     #[pallet::storage]
-    #[pallet::getter(fn hashes)]
-    pub(super) type Hashes<T: Config> =
-        StorageMap<_, frame_support::Blake2_128Concat, FileHash, T::AccountId>;
+    #[pallet::getter(fn deleted_files)]
+    pub(super) type DeletedFiles<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, ()>;
+
+    /// removal cursor of the licenses of the first deleted file
+    #[pallet::storage]
+    #[pallet::getter(fn deleted_file_cursor)]
+    pub(super) type DeletedFileCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn challenges)]
+    pub(super) type Challenges<T: Config> = StorageMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        StorageChallenge<T::AccountId, BlockNumber<T>>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn missed_challenges)]
+    pub(super) type MissedChallenges<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// raw key of the last file charged for storage fee
+    #[pallet::storage]
+    #[pallet::getter(fn storage_fee_cursor)]
+    pub(super) type StorageFeeCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    /// raw key of the last file translated to the v1 layout, set by the v1
+    /// migration and removed once every existing file is translated
+    #[pallet::storage]
+    #[pallet::getter(fn file_layout_migration_cursor)]
+    pub(super) type FileLayoutMigrationCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    /// raw key of the last file indexed by owner & uploader, set by the v2
+    /// migration and removed once every existing file is indexed
+    #[pallet::storage]
+    #[pallet::getter(fn file_index_backfill_cursor)]
+    pub(super) type FileIndexBackfillCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn files_by_attester)]
+    pub(super) type FilesByAttester<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        AttesterId,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        (),
+    >;
+
+    /// revoked attesters whose replicas are still being flagged (on idle)
+    #[pallet::storage]
+    #[pallet::getter(fn pending_replica_revocations)]
+    pub(super) type PendingReplicaRevocations<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, AttesterId, ()>;
+
+    /// raw key of the last file flagged for the first pending revocation
+    #[pallet::storage]
+    #[pallet::getter(fn replica_revocation_cursor)]
+    pub(super) type ReplicaRevocationCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn files_by_owner)]
+    pub(super) type FilesByOwner<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId, // owner
+        frame_support::Blake2_128Concat,
+        T::AccountId, // file
+        (),
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn owned_file_counts)]
+    pub(super) type OwnedFileCounts<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn files_by_uploader)]
+    pub(super) type FilesByUploader<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId, // uploader
+        frame_support::Blake2_128Concat,
+        T::AccountId, // file
+        (),
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn uploaded_file_counts)]
+    pub(super) type UploadedFileCounts<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn listings)]
+    pub(super) type Listings<T: Config> = StorageMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        Listing<BalanceCurrencyTypeOf<T>, BlockNumber<T>>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn offers)]
+    pub(super) type Offers<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId, // file
+        frame_support::Blake2_128Concat,
+        T::AccountId,             // buyer
+        BalanceCurrencyTypeOf<T>, // reserved on the buyer
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn offer_counts)]
+    pub(super) type OfferCounts<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn highest_bids)]
+    pub(super) type HighestBids<T: Config> = StorageMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        Bid<T::AccountId, BalanceCurrencyTypeOf<T>>, // reserved on the bidder
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn accepted_assets)]
+    pub(super) type AcceptedAssets<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, AssetIdOf<T>, AcceptedAsset>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_pricing)]
+    pub(super) type PendingPricing<T: Config> = StorageMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        PricingUpdate<BalanceCurrencyTypeOf<T>, BlockNumber<T>>,
+    >;
 
     // endregion
 
     // region: Genesis
 
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// file information, the file account is derived from the content hash
+        /// & uploader, upload fee is taken from the uploader's genesis balance
+        pub files: sp_std::vec::Vec<FileInformationArgs<T::AccountId, BalanceCurrencyTypeOf<T>>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for args in &self.files {
+                let file = Pallet::<T>::file_account_of(&args.hash, &args.uploader);
+                Pallet::<T>::upload_file(file, args.clone(), None)
+                    .expect("Initial File is invalid!");
+            }
+        }
+    }
+
     // endregion
 
     // region: Custom, Event, and Errors type
 
     /// File Information
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct FileInformation<T: Config> {
+        pub hash: FileHash,
+        pub uploader: T::AccountId,
+        pub big_brother: T::AccountId,
+        pub owner: T::AccountId,
+        pub transfer_fee: BalanceCurrencyTypeOf<T>,
+        pub download_fee: Option<BalanceCurrencyTypeOf<T>>,
+        pub size: u64,
+        pub chunk_size: u32,
+        pub chunk_root: FileHash, // merkle root over the chunks
+        pub pricing_asset: Option<AssetIdOf<T>>, // None means native currency
+        pub replication_factor: u32, // replicas wanted, may be more than kept
+        pub replicas: BoundedVec<FileReplica<T::AccountId>, T::MaxReplicas>,
+        pub royalties: BoundedVec<(T::AccountId, sp_runtime::Permill), T::MaxRoyaltyBeneficiaries>,
+        pub last_charged_at: BlockNumber<T>, // storage fee, upload counts as charged
+        pub status: FileStatus<BlockNumber<T>>,
+    }
+
+    /// File Replica, a servicer keeping a copy of the file behind an attester
     #[derive(Clone, Eq, PartialEq)]
     #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(serde::Deserialize, serde::Serialize)]
     #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
-    pub struct FileInformation<AccountId, FeeInToken>
-    where
-        FeeInToken: Clone + Copy,
-        AccountId: Clone, {
+    pub struct FileReplica<AccountId> {
+        pub servicer: AccountId,
+        pub attester: ngr_svrg::AttesterId,
+        pub attester_revoked: bool, // replica should be replaced
+    }
+
+    /// File Access Mode, defaults to `Paid` (or `Public` for FFA files)
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub enum AccessMode {
+        /// Anyone, free of charge
+        Public,
+        /// Anyone paying the download fee
+        Paid,
+        /// Only accounts granted on the access list, paying the download fee
+        Allowlist,
+        /// Anyone paying the download fee, except accounts on the access list
+        Denylist,
+    }
+
+    /// Download License (receipt of a paid download)
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct License<BlockNumber> {
+        pub purchased_at: BlockNumber,
+        pub expires_at: BlockNumber,
+        pub downloads: u32,
+    }
+
+    impl<BlockNumber: PartialOrd> License<BlockNumber> {
+        pub fn is_valid_at(&self, now: BlockNumber) -> bool {
+            now < self.expires_at
+        }
+    }
+
+    /// Storage Challenge, the challenged replica must prove it still keeps
+    /// the chunk
+    #[derive(Clone, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct StorageChallenge<AccountId, BlockNumber> {
+        pub servicer: AccountId,
+        pub attester: ngr_svrg::AttesterId,
+        pub chunk_index: u32,
+        pub issued_at: BlockNumber,
+        pub deadline: BlockNumber,
+    }
+
+    /// Sale Listing of a file, a file without one is not for sale
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub enum Listing<Balance, BlockNumber> {
+        /// Anyone may buy the file instantly at the ask price
+        FixedPrice { price: Balance },
+        /// English auction, the highest bid wins once `end` is reached
+        Auction {
+            reserve_price: Balance,
+            end: BlockNumber,
+        },
+    }
+
+    /// Auction Bid, the amount is reserved on the bidder until outbid
+    #[derive(Clone, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct Bid<AccountId, Balance> {
+        pub bidder: AccountId,
+        pub amount: Balance,
+    }
+
+    /// Pricing Update scheduled by the owner, buyers keep seeing the current
+    /// terms until `effective_at`
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct PricingUpdate<Balance, BlockNumber> {
+        pub transfer_fee: Balance,
+        pub download_fee: Option<Balance>, // None means FFA
+        pub effective_at: BlockNumber,
+    }
+
+    /// Accepted Asset for file pricing, protocol fees (upload, storage) are
+    /// converted from the native currency by `rate`
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct AcceptedAsset {
+        pub rate: sp_runtime::FixedU128, // asset units per native unit
+        pub retired: bool,               // no new uploads, existing files keep paying
+    }
+
+    /// File Summary, the file information with its sale & pricing state,
+    /// independent of the runtime config (for runtime API & RPC clients)
+    #[derive(Clone, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct FileSummary<AccountId, AssetId, Balance, BlockNumber> {
         pub hash: FileHash,
         pub uploader: AccountId,
         pub big_brother: AccountId,
-        pub servicer: AccountId,
-        pub attester: ngr_svrg::AttesterId,
         pub owner: AccountId,
-        pub transfer_fee: FeeInToken,
-        pub download_fee: Option<FeeInToken>,
+        pub pricing_asset: Option<AssetId>,
+        pub transfer_fee: Balance,
+        pub download_fee: Option<Balance>,
         pub size: u64,
+        pub chunk_size: u32,
+        pub chunk_root: FileHash,
+        pub replication_factor: u32,
+        pub replicas: sp_std::vec::Vec<FileReplica<AccountId>>,
+        pub royalties: sp_std::vec::Vec<(AccountId, sp_runtime::Permill)>,
+        pub last_charged_at: BlockNumber,
+        pub status: FileStatus<BlockNumber>,
+        pub listing: Option<Listing<Balance, BlockNumber>>,
+        pub pending_pricing: Option<PricingUpdate<Balance, BlockNumber>>,
     }
 
-    impl<AccountId, FeeInToken> FileInformation<AccountId, FeeInToken>
-    where
-        FeeInToken: Clone + Copy,
-        AccountId: Clone,
-    {
+    /// Download Access of an account to a file
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub enum DownloadAccess<Balance> {
+        /// Free of charge (FFA or public file, or valid license)
+        Free,
+        /// Download fee to pay (in the file pricing currency), a license is
+        /// issued along
+        Purchase { fee: Balance },
+        /// Delinquent file, or denied by its access policy
+        Denied,
+    }
+
+    /// Fee Quote of protocol fees for a file size, in the native currency or
+    /// in a pricing asset
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct FeeQuote<Balance> {
+        pub upload: Balance,
+        pub min_download: Balance,
+        pub storage_per_period: Balance,
+        pub min_transfer: Balance, // lowest ask price an upload may set, regardless of the size
+    }
+
+    /// File Quote of the current costs of a file, in its pricing currency
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct FileQuote<Balance> {
+        pub download: Option<Balance>, // None means FFA
+        pub transfer: Option<Balance>, // None means not for sale at a fixed price
+        pub min_bid: Option<Balance>,  // None means no auction open for bids
+        pub storage_per_period: Balance,
+    }
+
+    /// Withdrawn fee, in the native currency or in the file pricing asset
+    pub enum FeeCredit<T: Config> {
+        Native(NegativeImbalanceTypeOf<T>),
+        Asset(AssetCreditOf<T>),
+    }
+
+    /// File Status
+    #[derive(Clone, Copy, Default, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub enum FileStatus<BlockNumber> {
+        #[default]
+        Active,
+        /// File account couldn't cover the storage fee, downloads are blocked
+        /// until topped up, deleted once the grace period is over
+        Delinquent { since: BlockNumber },
+    }
+
+    impl<T: Config> FileInformation<T> {
         pub fn is_ffa(&self) -> bool {
             self.download_fee.is_none()
         }
+
+        pub fn is_storage_fee_due(&self, now: BlockNumber<T>, period: BlockNumber<T>) -> bool {
+            now >= sp_runtime::traits::Saturating::saturating_add(self.last_charged_at, period)
+        }
+
+        pub fn is_delinquent(&self) -> bool {
+            matches!(self.status, FileStatus::Delinquent { .. })
+        }
+
+        pub fn chunk_count(&self) -> u32 {
+            ngr_chmk::chunk_count(self.size, self.chunk_size)
+        }
+
+        /// files uploaded before chunk manifests (migrated) have none until a
+        /// servicer sets it, they can't be challenged meanwhile
+        pub fn has_chunk_manifest(&self) -> bool {
+            self.chunk_size > 0
+        }
+
+        /// verify a chunk & its inclusion proof against the chunk root
+        pub fn verify_chunk(&self, index: u32, chunk: &[u8], proof: &[FileHash]) -> bool {
+            ngr_chmk::verify_chunk(
+                &self.chunk_root,
+                self.size,
+                self.chunk_size,
+                index,
+                chunk,
+                proof,
+            )
+        }
+
+        pub fn is_under_replicated(&self) -> bool {
+            (self.replicas.len() as u32) < self.replication_factor
+        }
+
+        pub fn replica_pairs(&self) -> sp_std::vec::Vec<(T::AccountId, AttesterId)> {
+            self.replicas
+                .iter()
+                .map(|replica| (replica.servicer.clone(), replica.attester))
+                .collect()
+        }
     }
 
     #[derive(Clone, Eq, PartialEq)]
-    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(serde::Deserialize, serde::Serialize)]
     #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
     pub struct FileInformationArgs<AccountId, FeeInToken>
     where
@@ -132,11 +654,15 @@ pub mod pallet {
         pub hash: FileHash,
         pub uploader: AccountId,
         pub big_brother: AccountId,
-        pub servicer: AccountId,
-        pub attester: ngr_svrg::AttesterId,
-        pub transfer_fee: FeeInToken,
+        pub replication_factor: u32,
+        pub replicas: sp_std::vec::Vec<(AccountId, ngr_svrg::AttesterId)>, // (servicer, attester)
+        // empty means an even split between uploader & big brother
+        pub royalties: sp_std::vec::Vec<(AccountId, sp_runtime::Permill)>,
+        pub transfer_fee: FeeInToken, // initial ask price, the file is listed for sale
         pub download_fee: Option<FeeInToken>,
         pub size: u64,
+        pub chunk_size: u32,
+        pub chunk_root: FileHash,
     }
 
     #[pallet::error]
@@ -151,6 +677,93 @@ pub mod pallet {
         OwnershipTransferFeeMustNotZero,
         /// Download fee is too low
         DownloadFeeTooLow,
+        /// Attester is revoked
+        AttesterRevoked,
+        /// Attester is inactive (missed heartbeats)
+        AttesterInactive,
+        /// Storage fee is already charged for the current period
+        StorageFeeNotDue,
+        /// File is delinquent (underfunded), top up the file account
+        FileDelinquent,
+        /// File is not delinquent
+        FileNotDelinquent,
+        /// Caller is not the file owner
+        NotFileOwner,
+        /// Access to the file is denied by its access policy
+        AccessDenied,
+        /// Access list entries exceeds runtime config
+        TooManyAccessListEntries,
+        /// Account is not on the file's access list
+        AccessListEntryNotFound,
+        /// Chunk size must be between one and the runtime maximum
+        InvalidChunkSize,
+        /// File has more chunks than a proof of the runtime maximum depth
+        /// covers
+        TooManyChunks,
+        /// Caller is not the challenged servicer of the file
+        NotFileServicer,
+        /// File already has a pending storage challenge
+        ChallengeAlreadyIssued,
+        /// File has no pending storage challenge
+        ChallengeNotFound,
+        /// Storage challenge deadline passed
+        ChallengeExpired,
+        /// Chunk inclusion proof is invalid
+        InvalidStorageProof,
+        /// File must have at least one replica
+        NoReplicas,
+        /// Replicas exceeds the replication factor or runtime config
+        TooManyReplicas,
+        /// Replication factor must be between the kept replicas and runtime
+        /// config
+        InvalidReplicationFactor,
+        /// Servicer or attester already keeps a replica of the file
+        DuplicateReplica,
+        /// Replica not found
+        ReplicaNotFound,
+        /// Attester is not binded to the replica servicer
+        ReplicaAttesterNotBinded,
+        /// Royalty beneficiaries exceeds runtime config
+        TooManyRoyaltyBeneficiaries,
+        /// Royalty shares must be non-zero and sum up to exactly 100%
+        InvalidRoyaltyShares,
+        /// Account is listed more than once in the royalty table
+        DuplicateRoyaltyBeneficiary,
+        /// File is not listed for sale at a fixed price
+        FileNotForSale,
+        /// Ask price is above the buyer's limit
+        PriceAboveLimit,
+        /// Owner can't buy, bid or make an offer on its own file
+        CannotBuyOwnFile,
+        /// File is on auction, settle it first
+        AuctionInProgress,
+        /// File is not on auction
+        AuctionNotFound,
+        /// Auction already ended
+        AuctionEnded,
+        /// Auction has not ended yet
+        AuctionNotEnded,
+        /// Auction duration must be between one block and runtime config
+        InvalidAuctionDuration,
+        /// Bid must meet the reserve price & exceed the highest bid
+        BidTooLow,
+        /// Offer amount must greater than zero
+        OfferMustNotZero,
+        /// Offer not found
+        OfferNotFound,
+        /// Pending offers exceeds runtime config
+        TooManyOffers,
+        /// Asset is not accepted (or retired) for file pricing
+        AssetNotAccepted,
+        /// Files owned (or uploaded) by the account exceeds runtime config
+        TooManyFilesPerAccount,
+        /// File already has a chunk manifest
+        ChunkManifestAlreadySet,
+        /// Existing files are still being migrated to the current layout
+        FileMigrationInProgress,
+        /// Entries of a deleted file at the same account are still being
+        /// removed
+        FileEntriesPendingRemoval,
     }
 
     #[pallet::event]
@@ -202,180 +815,1700 @@ pub mod pallet {
         },
         /// Insufficient amount to keep file existence, file will be deleted
         InsufficientAmountForKeepingFile { file: AccountTypeOf<T> },
+        /// Insufficient amount to pay storage fee, file is delinquent until
+        /// topped up, or deleted after `delete_at`
+        FileMarkedDelinquent {
+            file: AccountTypeOf<T>,
+            delete_at: BlockNumber<T>,
+        },
+        /// Delinquent file is topped up & reinstated
+        FileReinstated { file: AccountTypeOf<T> },
+        /// File access mode changed
+        AccessModeChanged {
+            file: AccountTypeOf<T>,
+            mode: AccessMode,
+        },
+        /// Account put on the file's access list (granted or denied, depending
+        /// on the access mode)
+        AccessListEntrySet {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            expires_at: Option<BlockNumber<T>>,
+        },
+        /// Account removed from the file's access list
+        AccessListEntryRemoved {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+        },
+        /// Download license purchased (or renewed)
+        LicensePurchased {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            expires_at: BlockNumber<T>,
+        },
+        /// File re-downloaded under a valid license, no fee charged
+        LicensedDownload {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            downloads: u32,
+        },
+        /// Storage challenge issued, fee is released on a valid answer
+        StorageChallengeIssued {
+            file: AccountTypeOf<T>,
+            servicer: AccountTypeOf<T>,
+            chunk_index: u32,
+            deadline: BlockNumber<T>,
+        },
+        /// Storage challenge answered with a valid proof
+        StorageChallengeAnswered {
+            file: AccountTypeOf<T>,
+            servicer: AccountTypeOf<T>,
+        },
+        /// Storage challenge missed, no fee released for the period
+        StorageChallengeMissed {
+            file: AccountTypeOf<T>,
+            servicer: AccountTypeOf<T>,
+            missed: u32,
+        },
+        /// Early termination fee distributed
+        EarlyTerminationFeeDistributed {
+            file: AccountTypeOf<T>,
+            to: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
+        /// File deleted by its owner, replicas may drop the bytes
+        FileDeletedByOwner {
+            file: AccountTypeOf<T>,
+            hash: FileHash,
+            replicas: sp_std::vec::Vec<(AccountTypeOf<T>, AttesterId)>,
+            refund: BalanceCurrencyTypeOf<T>,
+        },
         StorageFeePaid {
             file: AccountTypeOf<T>,
             amount: BalanceCurrencyTypeOf<T>,
         },
+        /// Storage fee held back for the period, the file has no chunk manifest
+        /// or no unrevoked replica to challenge
+        StorageFeeWithheld { file: AccountTypeOf<T> },
         /// Storage fee distributed
         StorageFeeDistributed {
             file: AccountTypeOf<T>,
             to: AccountTypeOf<T>,
             amount: BalanceCurrencyTypeOf<T>,
         },
-    }
-
-    // endregion
-
-    // region: Helper methods
-
-    impl<T: Config> Pallet<T> {
-        fn delete_file(file: &T::AccountId) -> Result<(), sp_runtime::DispatchError> {
-            if !<Files<T>>::contains_key(file) {
-                return Err(<Error<T>>::FileNotFound.into());
-            }
-
-            let FileInformation {
-                hash, ..
-            } = Self::files(file).unwrap();
-            <Files<T>>::remove(file);
-            <Hashes<T>>::remove(hash);
-
-            Self::deposit_event(Event::InsufficientAmountForKeepingFile {
-                file: file.clone(),
-            });
-
-            Ok(())
-        }
-
-        fn distribute_storage_fee(
-            file: &T::AccountId,
-            spread: bool,
-        ) -> Result<(), sp_runtime::DispatchError> {
+        /// File's attester revoked, the replica should be replaced
+        FileAttesterRevoked {
+            file: AccountTypeOf<T>,
+            attester: AttesterId,
+        },
+        /// Replica added to a file
+        ReplicaAdded {
+            file: AccountTypeOf<T>,
+            servicer: AccountTypeOf<T>,
+            attester: AttesterId,
+        },
+        /// Replica of a file replaced (e.g. the old one went offline)
+        ReplicaReplaced {
+            file: AccountTypeOf<T>,
+            from: (AccountTypeOf<T>, AttesterId),
+            to: (AccountTypeOf<T>, AttesterId),
+        },
+        /// File replication factor changed
+        ReplicationFactorChanged {
+            file: AccountTypeOf<T>,
+            replication_factor: u32,
+        },
+        /// File listed for sale at a fixed price
+        FileListed {
+            file: AccountTypeOf<T>,
+            price: BalanceCurrencyTypeOf<T>,
+        },
+        /// File is not for sale anymore
+        FileUnlisted { file: AccountTypeOf<T> },
+        /// Offer made (or changed), the amount is reserved on the buyer
+        OfferMade {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
+        /// Offer withdrawn & unreserved
+        OfferWithdrawn {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
+        /// Offer accepted by the owner, the file is sold
+        OfferAccepted {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
+        /// Auction started
+        AuctionStarted {
+            file: AccountTypeOf<T>,
+            reserve_price: BalanceCurrencyTypeOf<T>,
+            end: BlockNumber<T>,
+        },
+        /// Bid placed, the previous highest bid is unreserved
+        BidPlaced {
+            file: AccountTypeOf<T>,
+            who: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
+        /// Auction settled, sold to the winner (if any bid)
+        AuctionSettled {
+            file: AccountTypeOf<T>,
+            winner: Option<(AccountTypeOf<T>, BalanceCurrencyTypeOf<T>)>,
+        },
+        /// Pricing update scheduled, current terms apply until `effective_at`
+        PricingUpdateScheduled {
+            file: AccountTypeOf<T>,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            download_fee: Option<BalanceCurrencyTypeOf<T>>,
+            effective_at: BlockNumber<T>,
+        },
+        /// Pricing update took effect
+        PricingUpdated {
+            file: AccountTypeOf<T>,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            download_fee: Option<BalanceCurrencyTypeOf<T>>,
+        },
+        /// Asset accepted for file pricing (or its rate updated)
+        AssetAccepted {
+            asset: AssetIdOf<T>,
+            rate: sp_runtime::FixedU128,
+        },
+        /// Asset retired, no new uploads priced in it
+        AssetRetired { asset: AssetIdOf<T> },
+        /// Chunk manifest set on a file that had none
+        ChunkManifestSet {
+            file: AccountTypeOf<T>,
+            chunk_size: u32,
+            chunk_root: FileHash,
+        },
+        /// Auction closed without a sale, the winning bid could not be settled
+        /// and is unreserved
+        AuctionSettlementFailed {
+            file: AccountTypeOf<T>,
+            bidder: AccountTypeOf<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+            error: sp_runtime::DispatchError,
+        },
+    }
+
+    // endregion
+
+    // region: Hooks
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut consumed_weight = T::DbWeight::get().reads(1); // layout migration cursor
+
+            if <FileLayoutMigrationCursor<T>>::exists() {
+                // untranslated files can't be read, nothing else runs meanwhile
+                return consumed_weight.saturating_add(migrations::v1::migrate_file_layouts::<T>(
+                    now,
+                    remaining_weight.saturating_sub(consumed_weight),
+                ));
+            }
+
+            consumed_weight = consumed_weight.saturating_add(Self::backfill_file_indexes(
+                remaining_weight.saturating_sub(consumed_weight),
+            ));
+            consumed_weight = consumed_weight.saturating_add(Self::flag_revoked_replicas(
+                remaining_weight.saturating_sub(consumed_weight),
+            ));
+            consumed_weight = consumed_weight.saturating_add(Self::clear_deleted_file_entries(
+                remaining_weight.saturating_sub(consumed_weight),
+            ));
+
+            consumed_weight.saturating_add(Self::charge_due_storage_fees(
+                now,
+                remaining_weight.saturating_sub(consumed_weight),
+            ))
+        }
+    }
+
+    // endregion
+
+    // region: Helper methods
+
+    impl<T: Config> Pallet<T> {
+        /// account keeping escrowed asset offers & bids
+        fn escrow_account() -> T::AccountId {
+            sp_runtime::traits::AccountIdConversion::into_account_truncating(&PALLET_IDENTIFICATION)
+        }
+
+        /// convert a native protocol fee into the pricing asset of a file
+        pub fn protocol_fee_in(
+            asset: Option<AssetIdOf<T>>,
+            fee: BalanceCurrencyTypeOf<T>,
+        ) -> Result<BalanceCurrencyTypeOf<T>, sp_runtime::DispatchError> {
+            let Some(asset) = asset else {
+                return Ok(fee);
+            };
+            let accepted_asset =
+                Self::accepted_assets(asset).ok_or(<Error<T>>::AssetNotAccepted)?;
+            let fee: u128 = sp_runtime::traits::UniqueSaturatedInto::unique_saturated_into(fee);
+            let fee = sp_runtime::FixedPointNumber::saturating_mul_int(accepted_asset.rate, fee);
+
+            Ok(sp_runtime::traits::UniqueSaturatedFrom::unique_saturated_from(fee))
+        }
+
+        fn free_balance_in(
+            asset: Option<AssetIdOf<T>>,
+            who: &T::AccountId,
+        ) -> BalanceCurrencyTypeOf<T> {
+            match asset {
+                | None => <<T as Config>::Currency as frame_support::traits::Currency<
+                    T::AccountId,
+                >>::free_balance(who),
+                | Some(asset) => {
+                    <T::Assets as frame_support::traits::fungibles::Inspect<T::AccountId>>::balance(
+                        asset, who,
+                    )
+                },
+            }
+        }
+
+        fn minimum_balance_in(asset: Option<AssetIdOf<T>>) -> BalanceCurrencyTypeOf<T> {
+            match asset {
+                | None => <<T as Config>::Currency as frame_support::traits::Currency<
+                    T::AccountId,
+                >>::minimum_balance(),
+                | Some(asset) => <T::Assets as frame_support::traits::fungibles::Inspect<
+                    T::AccountId,
+                >>::minimum_balance(asset),
+            }
+        }
+
+        fn preservation_of(
+            existence_requirement: frame_support::traits::tokens::ExistenceRequirement,
+        ) -> frame_support::traits::tokens::Preservation {
+            match existence_requirement {
+                | frame_support::traits::tokens::ExistenceRequirement::KeepAlive => {
+                    frame_support::traits::tokens::Preservation::Preserve
+                },
+                | frame_support::traits::tokens::ExistenceRequirement::AllowDeath => {
+                    frame_support::traits::tokens::Preservation::Expendable
+                },
+            }
+        }
+
+        /// withdraw a fee in the native currency or in a pricing asset
+        fn withdraw_fee(
+            asset: Option<AssetIdOf<T>>,
+            who: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+            existence_requirement: frame_support::traits::tokens::ExistenceRequirement,
+        ) -> Result<FeeCredit<T>, sp_runtime::DispatchError> {
+            match asset {
+                | None => <<T as Config>::Currency as frame_support::traits::Currency<
+                    T::AccountId,
+                >>::withdraw(
+                    who,
+                    amount,
+                    frame_support::traits::tokens::WithdrawReasons::FEE,
+                    existence_requirement,
+                )
+                .map(FeeCredit::Native),
+                | Some(asset) => <T::Assets as frame_support::traits::fungibles::Balanced<
+                    T::AccountId,
+                >>::withdraw(
+                    asset,
+                    who,
+                    amount,
+                    frame_support::traits::tokens::Precision::Exact,
+                    Self::preservation_of(existence_requirement),
+                    frame_support::traits::tokens::Fortitude::Polite,
+                )
+                .map(FeeCredit::Asset),
+            }
+        }
+
+        /// hand the remainder of a distributed fee to its handler
+        fn settle_fee_remainder(fee: FeeCredit<T>) {
+            match fee {
+                | FeeCredit::Native(fee) => {
+                    <T::OnFeeRemainder as frame_support::traits::OnUnbalanced<_>>::on_unbalanced(
+                        fee,
+                    );
+                },
+                | FeeCredit::Asset(fee) => {
+                    <T::OnAssetFeeRemainder as frame_support::traits::OnUnbalanced<
+                        AssetCreditOf<T>,
+                    >>::on_unbalanced(fee);
+                },
+            }
+        }
+
+        fn transfer_in(
+            asset: Option<AssetIdOf<T>>,
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+            existence_requirement: frame_support::traits::tokens::ExistenceRequirement,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            match asset {
+                | None => <<T as Config>::Currency as frame_support::traits::Currency<
+                    T::AccountId,
+                >>::transfer(from, to, amount, existence_requirement),
+                | Some(asset) => {
+                    <T::Assets as frame_support::traits::fungibles::Mutate<T::AccountId>>::transfer(
+                        asset,
+                        from,
+                        to,
+                        amount,
+                        Self::preservation_of(existence_requirement),
+                    )
+                    .map(|_| ())
+                },
+            }
+        }
+
+        /// escrow an offer or bid, native amounts are reserved, asset amounts
+        /// are moved to the escrow account
+        fn reserve_in(
+            asset: Option<AssetIdOf<T>>,
+            who: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            match asset {
+                | None => {
+                    <<T as Config>::Currency as frame_support::traits::ReservableCurrency<
+                        T::AccountId,
+                    >>::reserve(who, amount)
+                },
+                | Some(_) => {
+                    Self::transfer_in(
+                        asset,
+                        who,
+                        &Self::escrow_account(),
+                        amount,
+                        frame_support::traits::tokens::ExistenceRequirement::KeepAlive,
+                    )
+                },
+            }
+        }
+
+        fn unreserve_in(
+            asset: Option<AssetIdOf<T>>,
+            who: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) {
+            match asset {
+                | None => {
+                    <<T as Config>::Currency as frame_support::traits::ReservableCurrency<
+                        T::AccountId,
+                    >>::unreserve(who, amount);
+                },
+                | Some(_) => {
+                    let _ = Self::transfer_in(
+                        asset,
+                        &Self::escrow_account(),
+                        who,
+                        amount,
+                        frame_support::traits::tokens::ExistenceRequirement::AllowDeath,
+                    );
+                },
+            }
+        }
+
+        fn delete_file(file: &T::AccountId) -> Result<(), sp_runtime::DispatchError> {
+            if !<Files<T>>::contains_key(file) {
+                return Err(<Error<T>>::FileNotFound.into());
+            }
+
+            let file_info = Self::files(file).unwrap();
+            Self::remove_file_entries(file, &file_info);
+
+            Self::deposit_event(Event::InsufficientAmountForKeepingFile {
+                file: file.clone(),
+            });
+
+            Ok(())
+        }
+
+        fn remove_file_entries(file: &T::AccountId, file_info: &FileInformation<T>) {
+            <Files<T>>::remove(file);
+            <Hashes<T>>::remove(file_info.hash);
+            Self::unindex_file_of_owner(&file_info.owner, file);
+            Self::unindex_file_of_uploader(&file_info.uploader, file);
+            for replica in file_info.replicas.iter() {
+                <FilesByAttester<T>>::remove(replica.attester, file);
+            }
+            <AccessModes<T>>::remove(file);
+            <AccessListSizes<T>>::remove(file);
+            <DeletedFiles<T>>::insert(file, ()); // licenses are unbounded, removed on idle
+            <Challenges<T>>::remove(file);
+            <PendingPricing<T>>::remove(file);
+            Self::release_sale_escrows(file, file_info.pricing_asset);
+        }
+
+        fn ensure_valid_pricing(
+            asset: Option<AssetIdOf<T>>,
+            size: u64,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            download_fee: Option<BalanceCurrencyTypeOf<T>>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(
+                !sp_runtime::traits::Zero::is_zero(&transfer_fee),
+                <Error<T>>::OwnershipTransferFeeMustNotZero,
+            );
+            if let Some(download_fee) = download_fee {
+                let min_download_fee =
+                    Self::protocol_fee_in(asset, T::MinDownloadFeePerByte::bytes_to_fee(size))?;
+                ensure!(
+                    download_fee >= min_download_fee,
+                    <Error<T>>::DownloadFeeTooLow
+                );
+            }
+
+            Ok(())
+        }
+
+        fn schedule_pricing_update(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            download_fee: Option<BalanceCurrencyTypeOf<T>>,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let file_info = Self::ensure_and_get_file_of_owner(file, owner)?;
+            Self::ensure_valid_pricing(
+                file_info.pricing_asset,
+                file_info.size,
+                transfer_fee,
+                download_fee,
+            )?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let effective_at =
+                sp_runtime::traits::Saturating::saturating_add(now, T::PricingNoticePeriod::get());
+            <PendingPricing<T>>::insert(
+                file,
+                PricingUpdate {
+                    transfer_fee,
+                    download_fee,
+                    effective_at,
+                },
+            );
+
+            Ok(Event::PricingUpdateScheduled {
+                file: file.clone(),
+                transfer_fee,
+                download_fee,
+                effective_at,
+            })
+        }
+
+        /// apply the pending pricing update of a file once its notice period
+        /// is over, a fixed price listing (asking the transfer fee) follows
+        /// the new transfer fee
+        fn apply_due_pricing_update(file: &T::AccountId) {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let Some(update) = Self::pending_pricing(file) else {
+                return;
+            };
+
+            if now < update.effective_at {
+                return;
+            }
+
+            <PendingPricing<T>>::remove(file);
+            let Some(previous_transfer_fee) = <Files<T>>::mutate(file, |mutable_file| {
+                let mutable_file = mutable_file.as_mut()?;
+                let previous_transfer_fee = mutable_file.transfer_fee;
+                mutable_file.transfer_fee = update.transfer_fee;
+                mutable_file.download_fee = update.download_fee;
+
+                Some(previous_transfer_fee)
+            }) else {
+                return;
+            };
+            <Listings<T>>::mutate(file, |listing| {
+                match listing.as_mut() {
+                    | Some(Listing::FixedPrice {
+                        price,
+                    }) if price.eq(&previous_transfer_fee) => *price = update.transfer_fee,
+                    | _ => {},
+                }
+            });
+            Self::deposit_event(Event::PricingUpdated {
+                file: file.clone(),
+                transfer_fee: update.transfer_fee,
+                download_fee: update.download_fee,
+            });
+        }
+
+        /// unlist a file & unreserve all of its offers & the highest bid
+        fn release_sale_escrows(file: &T::AccountId, asset: Option<AssetIdOf<T>>) {
+            <Listings<T>>::remove(file);
+            <OfferCounts<T>>::remove(file);
+            for (buyer, amount) in <Offers<T>>::drain_prefix(file) {
+                Self::unreserve_in(asset, &buyer, amount);
+            }
+            if let Some(bid) = <HighestBids<T>>::take(file) {
+                Self::unreserve_in(asset, &bid.bidder, bid.amount);
+            }
+        }
+
+        fn take_offer(
+            file: &T::AccountId,
+            buyer: &T::AccountId,
+        ) -> Option<BalanceCurrencyTypeOf<T>> {
+            let amount = <Offers<T>>::take(file, buyer)?;
+            <OfferCounts<T>>::mutate(file, |count| *count = count.saturating_sub(1));
+
+            Some(amount)
+        }
+
+        /// auction end of a file, `None` if the file is not on auction
+        fn auction_end_of(file: &T::AccountId) -> Option<BlockNumber<T>> {
+            match Self::listings(file) {
+                | Some(Listing::Auction {
+                    end, ..
+                }) => Some(end),
+                | _ => None,
+            }
+        }
+
+        fn can_cover_storage_fee(file: &T::AccountId, file_info: &FileInformation<T>) -> bool {
+            let asset = file_info.pricing_asset;
+            let native_fee = T::StorageFeePerBytePerPeriod::bytes_to_fee(file_info.size);
+            let Ok(fee) = Self::protocol_fee_in(asset, native_fee) else {
+                return false;
+            };
+            let free_balance = Self::free_balance_in(asset, file);
+            let minimum_balance = Self::minimum_balance_in(asset);
+
+            free_balance >= sp_runtime::traits::Saturating::saturating_add(fee, minimum_balance)
+        }
+
+        /// issue a random chunk challenge for a due file, or mark it delinquent
+        /// if it can't cover the fee anyway
+        fn try_issue_challenge(
+            file: &T::AccountId,
+            now: BlockNumber<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            ensure!(
+                file_info.is_storage_fee_due(now, T::StoragePeriod::get()),
+                <Error<T>>::StorageFeeNotDue,
+            );
+            ensure!(
+                !<Challenges<T>>::contains_key(file),
+                <Error<T>>::ChallengeAlreadyIssued,
+            );
+
+            if !Self::can_cover_storage_fee(file, &file_info) {
+                return Self::mark_delinquent_or_delete_file(file, now);
+            }
+
+            if !file_info.has_chunk_manifest() {
+                // nothing to challenge against, the fee is held back until a
+                // manifest is set
+                return Self::withhold_storage_fee(file, now);
+            }
+
+            let subject = codec::Encode::encode(&(PALLET_IDENTIFICATION, file, now));
+            let (random_hash, _) =
+                <T::Randomness as frame_support::traits::Randomness<_, _>>::random(&subject);
+            let (random_chunk, random_replica) = <(u32, u32) as codec::Decode>::decode(
+                &mut sp_runtime::traits::TrailingZeroInput::new(random_hash.as_ref()),
+            )
+            .unwrap_or_default();
+            let live_replicas = file_info
+                .replicas
+                .iter()
+                .filter(|replica| {
+                    !replica.attester_revoked
+                        && !ngr_svrg::Pallet::<T>::is_attester_revoked(&replica.attester)
+                })
+                .collect::<sp_std::vec::Vec<_>>();
+
+            if live_replicas.is_empty() {
+                // every replica is revoked, nobody to pay until one is replaced
+                return Self::withhold_storage_fee(file, now);
+            }
+
+            let replica_index = random_replica as usize % live_replicas.len();
+            let replica = live_replicas[replica_index].clone();
+            let chunk_index = random_chunk % file_info.chunk_count();
+            let deadline = sp_runtime::traits::Saturating::saturating_add(
+                now,
+                T::ChallengeResponsePeriod::get(),
+            );
+            <Challenges<T>>::insert(
+                file,
+                StorageChallenge {
+                    servicer: replica.servicer.clone(),
+                    attester: replica.attester,
+                    chunk_index,
+                    issued_at: now,
+                    deadline,
+                },
+            );
+            Self::deposit_event(Event::StorageChallengeIssued {
+                file: file.clone(),
+                servicer: replica.servicer,
+                chunk_index,
+                deadline,
+            });
+
+            Ok(())
+        }
+
+        /// let a storage period pass unpaid, no replica proved keeping the file
+        fn withhold_storage_fee(
+            file: &T::AccountId,
+            now: BlockNumber<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            <Files<T>>::try_mutate(file, |mutable_file| {
+                let mutable_file = mutable_file.as_mut().ok_or(<Error<T>>::FileNotFound)?;
+                mutable_file.last_charged_at = now;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            Self::deposit_event(Event::StorageFeeWithheld {
+                file: file.clone(),
+            });
+
+            Ok(())
+        }
+
+        fn set_chunk_manifest(
+            file: &T::AccountId,
+            chunk_size: u32,
+            chunk_root: FileHash,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            <Files<T>>::try_mutate(file, |mutable_file| {
+                let mutable_file = mutable_file.as_mut().ok_or(<Error<T>>::FileNotFound)?;
+                ensure!(
+                    !mutable_file.has_chunk_manifest(),
+                    <Error<T>>::ChunkManifestAlreadySet,
+                );
+                ensure!(
+                    chunk_size > 0 && chunk_size <= T::MaxChunkSize::get(),
+                    <Error<T>>::InvalidChunkSize,
+                );
+                let chunk_count = ngr_chmk::chunk_count(mutable_file.size, chunk_size);
+                ensure!(
+                    ngr_chmk::proof_depth(chunk_count) <= T::MaxProofDepth::get(),
+                    <Error<T>>::TooManyChunks,
+                );
+                mutable_file.chunk_size = chunk_size;
+                mutable_file.chunk_root = chunk_root;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+
+            Ok(Event::ChunkManifestSet {
+                file: file.clone(),
+                chunk_size,
+                chunk_root,
+            })
+        }
+
+        fn try_answer_challenge(
+            servicer: T::AccountId,
+            file: &T::AccountId,
+            chunk: &[u8],
+            proof: &[FileHash],
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            let challenge = Self::challenges(file).ok_or(<Error<T>>::ChallengeNotFound)?;
+            ensure!(
+                challenge.servicer.eq(&servicer),
+                <Error<T>>::NotFileServicer,
+            );
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now <= challenge.deadline, <Error<T>>::ChallengeExpired);
+            ensure!(
+                file_info.verify_chunk(challenge.chunk_index, chunk, proof),
+                <Error<T>>::InvalidStorageProof,
+            );
+            <Challenges<T>>::remove(file);
+            Self::deposit_event(Event::StorageChallengeAnswered {
+                file: file.clone(),
+                servicer: servicer.clone(),
+            });
+
+            Self::distribute_storage_fee(file, &servicer, &challenge.attester)
+        }
+
+        /// missed challenge: the period passes without fee, and counts against
+        /// the challenged replica
+        fn miss_challenge(
+            file: &T::AccountId,
+            now: BlockNumber<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            let challenge = Self::challenges(file).ok_or(<Error<T>>::ChallengeNotFound)?;
+            <Challenges<T>>::remove(file);
+            <Files<T>>::mutate(file, |mutable_file| {
+                if let Some(mutable_file) = mutable_file.as_mut() {
+                    mutable_file.last_charged_at = now;
+                }
+            });
+            let missed = <MissedChallenges<T>>::mutate(&challenge.servicer, |missed| {
+                *missed = missed.saturating_add(1);
+                *missed
+            });
+            Self::deposit_event(Event::StorageChallengeMissed {
+                file: file.clone(),
+                servicer: challenge.servicer,
+                missed,
+            });
+
+            // attester may already be unbinded, the miss is recorded regardless
+            let _ = ngr_svrg::Pallet::<T>::rep_decrease_by_attester_id(
+                file_info.big_brother,
+                challenge.attester,
+            );
+
+            Ok(())
+        }
+
+        /// valid download license of an account
+        pub fn active_license(
+            file: &T::AccountId,
+            who: &T::AccountId,
+        ) -> Option<License<BlockNumber<T>>> {
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            Self::licenses(file, who).filter(|license| license.is_valid_at(now))
+        }
+
+        /// how an account may download a file right now, a due pricing update
+        /// counts as applied
+        pub fn download_access(
+            file: &T::AccountId,
+            who: &T::AccountId,
+        ) -> Option<DownloadAccess<BalanceCurrencyTypeOf<T>>> {
+            let file_info = Self::files(file)?;
+
+            if file_info.is_delinquent() || !Self::has_access(file, &file_info, who) {
+                return Some(DownloadAccess::Denied);
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let download_fee = Self::pending_pricing(file)
+                .filter(|update| now >= update.effective_at)
+                .map_or(file_info.download_fee, |update| update.download_fee);
+            let access = match download_fee {
+                | Some(fee)
+                    if Self::access_mode_of(file, &file_info) != AccessMode::Public
+                        && Self::active_license(file, who).is_none() =>
+                {
+                    DownloadAccess::Purchase {
+                        fee,
+                    }
+                },
+                | _ => DownloadAccess::Free,
+            };
+
+            Some(access)
+        }
+
+        fn issue_license(file: &T::AccountId, who: &T::AccountId) {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let expires_at =
+                sp_runtime::traits::Saturating::saturating_add(now, T::LicenseDuration::get());
+            <Licenses<T>>::insert(
+                file,
+                who,
+                License {
+                    purchased_at: now,
+                    expires_at,
+                    downloads: 1,
+                },
+            );
+            Self::deposit_event(Event::LicensePurchased {
+                file: file.clone(),
+                who: who.clone(),
+                expires_at,
+            });
+        }
+
+        fn ensure_and_get_file_of_owner(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+        ) -> Result<FileInformation<T>, sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            ensure!(file_info.owner.eq(owner), <Error<T>>::NotFileOwner);
+
+            Ok(file_info)
+        }
+
+        /// access mode of a file, falls back to `Paid` (or `Public` for FFA
+        /// files)
+        pub fn access_mode_of(file: &T::AccountId, file_info: &FileInformation<T>) -> AccessMode {
+            Self::access_modes(file).unwrap_or(if file_info.is_ffa() {
+                AccessMode::Public
+            } else {
+                AccessMode::Paid
+            })
+        }
+
+        fn is_on_access_list(file: &T::AccountId, who: &T::AccountId) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            match Self::access_lists(file, who) {
+                | Some(Some(expires_at)) => now < expires_at,
+                | Some(None) => true,
+                | None => false,
+            }
+        }
+
+        /// check whether an account may access a file (the owner always may)
+        pub fn has_access(
+            file: &T::AccountId,
+            file_info: &FileInformation<T>,
+            who: &T::AccountId,
+        ) -> bool {
+            if file_info.owner.eq(who) {
+                return true;
+            }
+
+            match Self::access_mode_of(file, file_info) {
+                | AccessMode::Public | AccessMode::Paid => true,
+                | AccessMode::Allowlist => Self::is_on_access_list(file, who),
+                | AccessMode::Denylist => !Self::is_on_access_list(file, who),
+            }
+        }
+
+        /// summary of a file, for runtime API consumers
+        pub fn file_summary(file: &T::AccountId) -> Option<FileSummaryOf<T>> {
+            let file_info = Self::files(file)?;
+
+            Some(FileSummary {
+                hash: file_info.hash,
+                uploader: file_info.uploader,
+                big_brother: file_info.big_brother,
+                owner: file_info.owner,
+                pricing_asset: file_info.pricing_asset,
+                transfer_fee: file_info.transfer_fee,
+                download_fee: file_info.download_fee,
+                size: file_info.size,
+                chunk_size: file_info.chunk_size,
+                chunk_root: file_info.chunk_root,
+                replication_factor: file_info.replication_factor,
+                replicas: file_info.replicas.into_inner(),
+                royalties: file_info.royalties.into_inner(),
+                last_charged_at: file_info.last_charged_at,
+                status: file_info.status,
+                listing: Self::listings(file),
+                pending_pricing: Self::pending_pricing(file),
+            })
+        }
+
+        /// file account of a file, derived from its content hash & uploader as
+        /// a sub account of the pallet, clients can compute it offline
+        pub fn file_account_of(hash: &FileHash, uploader: &T::AccountId) -> T::AccountId {
+            derive_file_account(hash, uploader)
+        }
+
+        /// file account & summary of a file by its content hash
+        pub fn file_by_hash(hash: &FileHash) -> Option<(T::AccountId, FileSummaryOf<T>)> {
+            let file = Self::hashes(hash)?;
+            let summary = Self::file_summary(&file)?;
+
+            Some((file, summary))
+        }
+
+        pub fn files_of_owner(owner: &T::AccountId) -> sp_std::vec::Vec<T::AccountId> {
+            <FilesByOwner<T>>::iter_key_prefix(owner).collect()
+        }
+
+        pub fn files_of_uploader(uploader: &T::AccountId) -> sp_std::vec::Vec<T::AccountId> {
+            <FilesByUploader<T>>::iter_key_prefix(uploader).collect()
+        }
+
+        fn try_index_file_of_owner(
+            owner: &T::AccountId,
+            file: &T::AccountId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            <OwnedFileCounts<T>>::try_mutate(owner, |count| {
+                ensure!(
+                    *count < T::MaxFilesPerAccount::get(),
+                    <Error<T>>::TooManyFilesPerAccount,
+                );
+                *count = count.saturating_add(1);
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            <FilesByOwner<T>>::insert(owner, file, ());
+
+            Ok(())
+        }
+
+        fn unindex_file_of_owner(owner: &T::AccountId, file: &T::AccountId) {
+            if <FilesByOwner<T>>::take(owner, file).is_some() {
+                <OwnedFileCounts<T>>::mutate(owner, |count| *count = count.saturating_sub(1));
+            }
+        }
+
+        fn try_index_file_of_uploader(
+            uploader: &T::AccountId,
+            file: &T::AccountId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            <UploadedFileCounts<T>>::try_mutate(uploader, |count| {
+                ensure!(
+                    *count < T::MaxFilesPerAccount::get(),
+                    <Error<T>>::TooManyFilesPerAccount,
+                );
+                *count = count.saturating_add(1);
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            <FilesByUploader<T>>::insert(uploader, file, ());
+
+            Ok(())
+        }
+
+        fn unindex_file_of_uploader(uploader: &T::AccountId, file: &T::AccountId) {
+            if <FilesByUploader<T>>::take(uploader, file).is_some() {
+                <UploadedFileCounts<T>>::mutate(uploader, |count| *count = count.saturating_sub(1));
+            }
+        }
+
+        /// protocol fees of a file size, `None` if the asset is not accepted
+        pub fn quote(
+            size: u64,
+            pricing_asset: Option<AssetIdOf<T>>,
+        ) -> Option<FeeQuote<BalanceCurrencyTypeOf<T>>> {
+            let fee_in = |fee| Self::protocol_fee_in(pricing_asset, fee).ok();
+
+            Some(FeeQuote {
+                upload: fee_in(T::UploadFeePerByte::bytes_to_fee(size))?,
+                min_download: fee_in(T::MinDownloadFeePerByte::bytes_to_fee(size))?,
+                storage_per_period: fee_in(T::StorageFeePerBytePerPeriod::bytes_to_fee(size))?,
+                min_transfer: sp_runtime::traits::One::one(),
+            })
+        }
+
+        /// current costs of a file, a due pricing update counts as applied
+        pub fn file_quote(file: &T::AccountId) -> Option<FileQuote<BalanceCurrencyTypeOf<T>>> {
+            let file_info = Self::files(file)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let due_update =
+                Self::pending_pricing(file).filter(|update| now >= update.effective_at);
+            let (transfer, min_bid) = match Self::listings(file) {
+                | Some(Listing::FixedPrice {
+                    price,
+                }) => {
+                    (
+                        Some(due_update.map_or(price, |update| update.transfer_fee)),
+                        None,
+                    )
+                },
+                | Some(Listing::Auction {
+                    reserve_price,
+                    end,
+                }) if now < end => {
+                    // a bid must exceed the highest one, or at least meet the reserve price
+                    let min_bid = Self::highest_bids(file).map_or(reserve_price, |highest| {
+                        sp_runtime::traits::Saturating::saturating_add(
+                            highest.amount,
+                            sp_runtime::traits::One::one(),
+                        )
+                    });
+
+                    (None, Some(min_bid))
+                },
+                | _ => (None, None),
+            };
+            let storage_fee = T::StorageFeePerBytePerPeriod::bytes_to_fee(file_info.size);
+            let storage_fee = Self::protocol_fee_in(file_info.pricing_asset, storage_fee).ok()?;
+
+            Some(FileQuote {
+                download: due_update.map_or(file_info.download_fee, |update| update.download_fee),
+                transfer,
+                min_bid,
+                storage_per_period: storage_fee,
+            })
+        }
+
+        fn try_set_access_list_entry(
+            file: &T::AccountId,
+            who: &T::AccountId,
+            expires_at: Option<BlockNumber<T>>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            if !<AccessLists<T>>::contains_key(file, who) {
+                <AccessListSizes<T>>::try_mutate(file, |size| {
+                    ensure!(
+                        *size < T::MaxAccessListEntries::get(),
+                        <Error<T>>::TooManyAccessListEntries,
+                    );
+                    *size = size.saturating_add(1);
+
+                    Result::<(), sp_runtime::DispatchError>::Ok(())
+                })?;
+            }
+
+            <AccessLists<T>>::insert(file, who, expires_at);
+
+            Ok(())
+        }
+
+        fn try_remove_access_list_entry(
+            file: &T::AccountId,
+            who: &T::AccountId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(
+                <AccessLists<T>>::contains_key(file, who),
+                <Error<T>>::AccessListEntryNotFound,
+            );
+            <AccessLists<T>>::remove(file, who);
+            <AccessListSizes<T>>::mutate(file, |size| *size = size.saturating_sub(1));
+
+            Ok(())
+        }
+
+        fn owner_delete(
+            file: &T::AccountId,
+            owner: T::AccountId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let file_info = Self::ensure_and_get_file_of_owner(file, &owner)?;
+            Self::remove_file_entries(file, &file_info);
+
+            let asset = file_info.pricing_asset;
+            let balance = Self::free_balance_in(asset, file);
+            let termination_fee = T::EarlyTerminationFee::get().mul_floor(balance);
+
+            if !sp_runtime::traits::Zero::is_zero(&termination_fee) {
+                let mut fee = Self::withdraw_fee(
+                    asset,
+                    file,
+                    termination_fee,
+                    frame_support::traits::tokens::ExistenceRequirement::AllowDeath,
+                )?;
+
+                for (to, amount) in
+                    Self::split_fee_to_replicas(&mut fee, &file_info.replicas, termination_fee)
+                {
+                    Self::deposit_event(Event::EarlyTerminationFeeDistributed {
+                        file: file.clone(),
+                        to,
+                        amount,
+                    });
+                }
+                Self::settle_fee_remainder(fee);
+            }
+
+            let refund = Self::free_balance_in(asset, file);
+            Self::transfer_in(
+                asset,
+                file,
+                &owner,
+                refund,
+                frame_support::traits::tokens::ExistenceRequirement::AllowDeath,
+            )?;
+
+            Self::deposit_event(Event::FileDeletedByOwner {
+                file: file.clone(),
+                hash: file_info.hash,
+                replicas: file_info.replica_pairs(),
+                refund,
+            });
+
+            Ok(())
+        }
+
+        fn mark_delinquent_or_delete_file(
+            file: &T::AccountId,
+            now: BlockNumber<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            let grace_period = T::GracePeriod::get();
+
+            match file_info.status {
+                | FileStatus::Active => {
+                    <Files<T>>::mutate(file, |mutable_file| {
+                        if let Some(mutable_file) = mutable_file.as_mut() {
+                            mutable_file.status = FileStatus::Delinquent {
+                                since: now,
+                            };
+                        }
+                    });
+                    Self::deposit_event(Event::FileMarkedDelinquent {
+                        file: file.clone(),
+                        delete_at: sp_runtime::traits::Saturating::saturating_add(
+                            now,
+                            grace_period,
+                        ),
+                    });
+                },
+                | FileStatus::Delinquent {
+                    since,
+                } if now >= sp_runtime::traits::Saturating::saturating_add(since, grace_period) => {
+                    Self::delete_file(file)?;
+                },
+                | FileStatus::Delinquent {
+                    ..
+                } => {}, // still within grace period
+            }
+
+            Ok(())
+        }
+
+        /// split `amount` off a withdrawn fee and credit it to `to`, returns
+        /// the credited amount
+        fn split_fee_to(
+            fee: &mut FeeCredit<T>,
+            to: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> BalanceCurrencyTypeOf<T> {
+            match fee {
+                | FeeCredit::Native(fee) => {
+                    let whole_fee = sp_std::mem::replace(
+                        fee,
+                        <NegativeImbalanceTypeOf<T> as frame_support::traits::Imbalance<_>>::zero(),
+                    );
+                    let (part, remainder) =
+                        frame_support::traits::Imbalance::split(whole_fee, amount);
+                    let credited = frame_support::traits::Imbalance::peek(&part);
+                    *fee = remainder;
+                    <<T as Config>::Currency as frame_support::traits::Currency<
+                        T::AccountId,
+                    >>::resolve_creating(to, part);
+
+                    credited
+                },
+                | FeeCredit::Asset(fee) => {
+                    let asset = fee.asset();
+                    let whole_fee = sp_std::mem::replace(fee, <AssetCreditOf<T>>::zero(asset));
+                    let (part, remainder) = whole_fee.split(amount);
+                    let credited = part.peek();
+                    *fee = remainder;
+
+                    match <T::Assets as frame_support::traits::fungibles::Balanced<
+                        T::AccountId,
+                    >>::resolve(to, part)
+                    {
+                        | Ok(()) => credited,
+                        | Err(part) => {
+                            // recipient can't hold the asset, kept in the remainder
+                            let _ = fee.subsume(part);
+                            sp_runtime::traits::Zero::zero()
+                        },
+                    }
+                },
+            }
+        }
+
+        /// split `amount` off a withdrawn fee and credit it pro-rata to the
+        /// servicer's (cooperative) members, returns the credited members
+        fn split_fee_to_servicer(
+            fee: &mut FeeCredit<T>,
+            servicer: &T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> sp_std::vec::Vec<(T::AccountId, BalanceCurrencyTypeOf<T>)> {
+            let revenue_shares = ngr_svrg::Pallet::<T>::revenue_shares(servicer);
+            let total_shares = revenue_shares
+                .iter()
+                .fold(0u32, |total, (_, shares)| total.saturating_add(*shares));
+
+            revenue_shares
+                .into_iter()
+                .map(|(member, shares)| {
+                    let portion = sp_runtime::Perbill::from_rational(shares, total_shares);
+                    let credited = Self::split_fee_to(fee, &member, portion.mul_floor(amount));
+
+                    (member, credited)
+                })
+                .collect()
+        }
+
+        /// split `amount` off a withdrawn fee evenly across the file replicas
+        /// (each pro-rata to its servicer's members), returns the credited
+        /// members. Replicas of revoked attesters are not paid, their part is
+        /// left in the fee remainder
+        fn split_fee_to_replicas(
+            fee: &mut FeeCredit<T>,
+            replicas: &[FileReplica<T::AccountId>],
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> sp_std::vec::Vec<(T::AccountId, BalanceCurrencyTypeOf<T>)> {
+            let divider =
+                <BalanceCurrencyTypeOf<T> as sp_runtime::traits::SaturatedConversion>::saturated_from(replicas.len().max(1));
+            let amount_per_replica = sp_runtime::traits::CheckedDiv::checked_div(&amount, &divider)
+                .unwrap_or_else(sp_runtime::traits::Zero::zero);
+
+            // checked on the registry too, replicas are flagged some blocks later
+            replicas
+                .iter()
+                .filter(|replica| {
+                    !replica.attester_revoked
+                        && !ngr_svrg::Pallet::<T>::is_attester_revoked(&replica.attester)
+                })
+                .flat_map(|replica| {
+                    Self::split_fee_to_servicer(fee, &replica.servicer, amount_per_replica)
+                })
+                .collect()
+        }
+
+        /// split a royalty pool off a withdrawn fee by the file royalty table,
+        /// the rounding remainder of the pool goes to the uploader, returns the
+        /// credited beneficiaries
+        fn split_fee_to_royalties(
+            fee: &mut FeeCredit<T>,
+            file_info: &FileInformation<T>,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> sp_std::vec::Vec<(T::AccountId, BalanceCurrencyTypeOf<T>)> {
+            let mut distributed: BalanceCurrencyTypeOf<T> = sp_runtime::traits::Zero::zero();
+            let mut credited_beneficiaries = file_info
+                .royalties
+                .iter()
+                .map(|(beneficiary, share)| {
+                    let credited = Self::split_fee_to(fee, beneficiary, share.mul_floor(amount));
+                    distributed =
+                        sp_runtime::traits::Saturating::saturating_add(distributed, credited);
+
+                    (beneficiary.clone(), credited)
+                })
+                .collect::<sp_std::vec::Vec<_>>();
+            let remainder = sp_runtime::traits::Saturating::saturating_sub(amount, distributed);
+
+            if !sp_runtime::traits::Zero::is_zero(&remainder) {
+                let credited = Self::split_fee_to(fee, &file_info.uploader, remainder);
+                credited_beneficiaries.push((file_info.uploader.clone(), credited));
+            }
+
+            credited_beneficiaries
+        }
+
+        /// royalty table of a new file, defaults to an even split between the
+        /// uploader and the big brother
+        fn build_royalties(
+            args: &FileInformationArgs<T::AccountId, BalanceCurrencyTypeOf<T>>,
+        ) -> Result<
+            BoundedVec<(T::AccountId, sp_runtime::Permill), T::MaxRoyaltyBeneficiaries>,
+            sp_runtime::DispatchError,
+        > {
+            if args.royalties.is_empty() {
+                return BoundedVec::try_from(Self::default_royalties(
+                    &args.uploader,
+                    &args.big_brother,
+                ))
+                .map_err(|_| <Error<T>>::TooManyRoyaltyBeneficiaries.into());
+            }
+
+            let mut beneficiaries = sp_std::collections::btree_set::BTreeSet::new();
+            let mut total_parts = 0u32;
+
+            for (beneficiary, share) in &args.royalties {
+                ensure!(share.deconstruct() > 0, <Error<T>>::InvalidRoyaltyShares);
+                ensure!(
+                    beneficiaries.insert(beneficiary.clone()),
+                    <Error<T>>::DuplicateRoyaltyBeneficiary,
+                );
+                total_parts = total_parts.saturating_add(share.deconstruct());
+            }
+
+            ensure!(
+                total_parts == sp_runtime::Permill::one().deconstruct(),
+                <Error<T>>::InvalidRoyaltyShares,
+            );
+
+            BoundedVec::try_from(args.royalties.clone())
+                .map_err(|_| <Error<T>>::TooManyRoyaltyBeneficiaries.into())
+        }
+
+        /// even split between uploader & big brother, whole to the uploader if
+        /// it is the big brother itself
+        pub(crate) fn default_royalties(
+            uploader: &T::AccountId,
+            big_brother: &T::AccountId,
+        ) -> sp_std::vec::Vec<(T::AccountId, sp_runtime::Permill)> {
+            let half = sp_runtime::Permill::from_percent(50);
+
+            if uploader.eq(big_brother) {
+                sp_std::vec![(uploader.clone(), sp_runtime::Permill::one())]
+            } else {
+                sp_std::vec![(uploader.clone(), half), (big_brother.clone(), half)]
+            }
+        }
+
+        /// increase reputation of every non-revoked replica of a file
+        fn rep_increase_replicas(
+            big_brother: &T::AccountId,
+            replicas: &[FileReplica<T::AccountId>],
+        ) -> Result<(), sp_runtime::DispatchError> {
+            for replica in replicas {
+                if !ngr_svrg::Pallet::<T>::is_attester_revoked(&replica.attester) {
+                    ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(
+                        big_brother.clone(),
+                        replica.attester,
+                    )?;
+                }
+            }
+
+            Ok(())
+        }
+
+        /// index the files existing before the owner & uploader indexes, from
+        /// the backfill cursor, bounded by weight & per block limit
+        fn backfill_file_indexes(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let weight_per_file = db_weight.reads_writes(5, 4); // TODO: please benchmark
+            let mut consumed_weight = db_weight.reads_writes(1, 1);
+
+            if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_file)) {
+                return Weight::zero();
+            }
+
+            let Some(cursor) = <FileIndexBackfillCursor<T>>::get() else {
+                return db_weight.reads(1);
+            };
+
+            let max_files = remaining_weight
+                .saturating_sub(consumed_weight)
+                .ref_time()
+                .checked_div(weight_per_file.ref_time())
+                .unwrap_or(u64::MAX)
+                .min(T::MaxFileIndexBackfillsPerBlock::get() as u64);
+            let files = <Files<T>>::iter_from(cursor)
+                .take(max_files as usize)
+                .collect::<sp_std::vec::Vec<_>>();
+
+            match files.last() {
+                | Some((file, _)) if files.len() as u64 == max_files => {
+                    <FileIndexBackfillCursor<T>>::put(<Files<T>>::hashed_key_for(file));
+                },
+                | _ => <FileIndexBackfillCursor<T>>::kill(), // backfill done
+            }
+
+            for (file, file_info) in files {
+                consumed_weight = consumed_weight.saturating_add(weight_per_file);
+                Self::index_existing_file(&file, &file_info);
+            }
+
+            consumed_weight
+        }
+
+        /// index an existing file by owner & uploader, unbounded as the file
+        /// already exists
+        pub(crate) fn index_existing_file(file: &T::AccountId, file_info: &FileInformation<T>) {
+            // files uploaded or transferred since the upgrade are indexed already
+            if !<FilesByOwner<T>>::contains_key(&file_info.owner, file) {
+                <FilesByOwner<T>>::insert(&file_info.owner, file, ());
+                <OwnedFileCounts<T>>::mutate(&file_info.owner, |count| {
+                    *count = count.saturating_add(1)
+                });
+            }
+            if !<FilesByUploader<T>>::contains_key(&file_info.uploader, file) {
+                <FilesByUploader<T>>::insert(&file_info.uploader, file, ());
+                <UploadedFileCounts<T>>::mutate(&file_info.uploader, |count| {
+                    *count = count.saturating_add(1)
+                });
+            }
+        }
+
+        /// flag the replicas of the first pending revoked attester, from the
+        /// revocation cursor, bounded by weight & per block limit
+        fn flag_revoked_replicas(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let weight_per_file = db_weight.reads_writes(2, 1); // TODO: please benchmark
+            let mut consumed_weight = db_weight.reads_writes(2, 2);
+
+            if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_file)) {
+                return Weight::zero();
+            }
+
+            let Some(attester_id) = <PendingReplicaRevocations<T>>::iter_keys().next() else {
+                return db_weight.reads(1);
+            };
+
+            let max_files = remaining_weight
+                .saturating_sub(consumed_weight)
+                .ref_time()
+                .checked_div(weight_per_file.ref_time())
+                .unwrap_or(u64::MAX)
+                .min(T::MaxReplicaRevocationsPerBlock::get() as u64);
+            let files = match <ReplicaRevocationCursor<T>>::get() {
+                | Some(cursor) => <FilesByAttester<T>>::iter_key_prefix_from(attester_id, cursor),
+                | None => <FilesByAttester<T>>::iter_key_prefix(attester_id),
+            }
+            .take(max_files as usize)
+            .collect::<sp_std::vec::Vec<_>>();
+
+            match files.last() {
+                | Some(file) if files.len() as u64 == max_files => {
+                    <ReplicaRevocationCursor<T>>::put(<FilesByAttester<T>>::hashed_key_for(
+                        attester_id,
+                        file,
+                    ));
+                },
+                | _ => {
+                    // every replica of the attester is flagged
+                    <ReplicaRevocationCursor<T>>::kill();
+                    <PendingReplicaRevocations<T>>::remove(attester_id);
+                },
+            }
+
+            for file in files {
+                consumed_weight = consumed_weight.saturating_add(weight_per_file);
+
+                <Files<T>>::mutate(&file, |mutable_file| {
+                    if let Some(mutable_file) = mutable_file.as_mut() {
+                        for replica in mutable_file.replicas.iter_mut() {
+                            if replica.attester.eq(&attester_id) {
+                                replica.attester_revoked = true;
+                            }
+                        }
+                    }
+                });
+                Self::deposit_event(Event::FileAttesterRevoked {
+                    file,
+                    attester: attester_id,
+                });
+            }
+
+            consumed_weight
+        }
+
+        /// remove the access list & licenses of the first deleted file, the
+        /// licenses from the removal cursor, bounded by weight & per block
+        /// limit
+        fn clear_deleted_file_entries(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let weight_per_entry = db_weight.writes(1); // TODO: please benchmark
+            let mut consumed_weight = db_weight.reads_writes(2, 2);
+
+            if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_entry)) {
+                return Weight::zero();
+            }
+
+            let Some(file) = <DeletedFiles<T>>::iter_keys().next() else {
+                return db_weight.reads(1);
+            };
+
+            let max_entries = remaining_weight
+                .saturating_sub(consumed_weight)
+                .ref_time()
+                .checked_div(weight_per_entry.ref_time())
+                .unwrap_or(u64::MAX)
+                .min(T::MaxFileEntryRemovalsPerBlock::get() as u64)
+                as u32;
+            let access_list = <AccessLists<T>>::clear_prefix(&file, max_entries, None);
+            consumed_weight = consumed_weight
+                .saturating_add(weight_per_entry.saturating_mul(access_list.unique.into()));
+            let max_entries = max_entries.saturating_sub(access_list.unique);
+
+            if access_list.maybe_cursor.is_some() || max_entries == 0 {
+                return consumed_weight;
+            }
+
+            let cursor = <DeletedFileCursor<T>>::get();
+            let licenses = <Licenses<T>>::clear_prefix(&file, max_entries, cursor.as_deref());
+            consumed_weight = consumed_weight
+                .saturating_add(weight_per_entry.saturating_mul(licenses.unique.into()));
+
+            match licenses.maybe_cursor {
+                | Some(cursor) => <DeletedFileCursor<T>>::put(cursor),
+                | None => {
+                    // every entry of the file is removed
+                    <DeletedFileCursor<T>>::kill();
+                    <DeletedFiles<T>>::remove(&file);
+                },
+            }
+
+            consumed_weight
+        }
+
+        /// challenge due files (and expire missed challenges) round-robin,
+        /// bounded by weight & per block limit
+        fn charge_due_storage_fees(now: BlockNumber<T>, remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let weight_per_file = db_weight.reads_writes(8, 8); // TODO: please benchmark
+            let mut consumed_weight = db_weight.reads_writes(1, 1);
+
+            if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_file)) {
+                return Weight::zero();
+            }
+
+            let max_files = remaining_weight
+                .saturating_sub(consumed_weight)
+                .ref_time()
+                .checked_div(weight_per_file.ref_time())
+                .unwrap_or(u64::MAX)
+                .min(T::MaxStorageFeeChargesPerBlock::get() as u64);
+            let files = match <StorageFeeCursor<T>>::get() {
+                | Some(cursor) => <Files<T>>::iter_from(cursor),
+                | None => <Files<T>>::iter(),
+            }
+            .take(max_files as usize)
+            .collect::<sp_std::vec::Vec<_>>();
+
+            match files.last() {
+                | Some((file, _)) if files.len() as u64 == max_files => {
+                    <StorageFeeCursor<T>>::put(<Files<T>>::hashed_key_for(file));
+                },
+                | _ => <StorageFeeCursor<T>>::kill(), // sweep done, restart next time
+            }
+
+            let period = T::StoragePeriod::get();
+
+            for (file, file_info) in files {
+                consumed_weight = consumed_weight.saturating_add(db_weight.reads(2));
+
+                let _ = match Self::challenges(&file) {
+                    | Some(challenge) if now > challenge.deadline => {
+                        frame_support::storage::with_storage_layer(|| {
+                            Self::miss_challenge(&file, now)
+                        })
+                    },
+                    | None if file_info.is_storage_fee_due(now, period) => {
+                        frame_support::storage::with_storage_layer(|| {
+                            Self::try_issue_challenge(&file, now)
+                        })
+                    },
+                    | _ => continue,
+                };
+
+                consumed_weight = consumed_weight.saturating_add(weight_per_file);
+            }
+
+            consumed_weight
+        }
+
+        /// charge the storage fee of a period, split between the big brother
+        /// and the replica which answered the challenge
+        fn distribute_storage_fee(
+            file: &T::AccountId,
+            servicer: &T::AccountId,
+            attester: &AttesterId,
+        ) -> Result<(), sp_runtime::DispatchError> {
             if !<Files<T>>::contains_key(file) {
                 return Err(<Error<T>>::FileNotFound.into());
             }
 
+            let the_file = Self::files(file).unwrap();
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                the_file.is_storage_fee_due(now, T::StoragePeriod::get()),
+                <Error<T>>::StorageFeeNotDue,
+            );
+
             let FileInformation {
                 big_brother,
-                servicer,
                 size,
-                attester,
+                pricing_asset,
                 ..
-            } = Self::files(file).unwrap();
-            let withdraw_reason = frame_support::traits::tokens::WithdrawReasons::FEE;
-            let total_fee = T::StorageFeePerBytePerPeriod::bytes_to_fee(size);
-            let maybe_success = <<T as Config>::Currency as frame_support::traits::Currency<
-                T::AccountId,
-            >>::withdraw(
+            } = the_file;
+            let native_fee = T::StorageFeePerBytePerPeriod::bytes_to_fee(size);
+            let total_fee = Self::protocol_fee_in(pricing_asset, native_fee)?;
+            let maybe_fee = Self::withdraw_fee(
+                pricing_asset,
                 file,
                 total_fee,
-                withdraw_reason,
                 frame_support::traits::tokens::ExistenceRequirement::KeepAlive,
             );
 
-            if maybe_success.is_err() {
-                Self::delete_file(file)?;
+            let Ok(mut fee) = maybe_fee else {
+                return Self::mark_delinquent_or_delete_file(file, now);
+            };
 
-                return Ok(());
-            }
+            <Files<T>>::mutate(file, |mutable_file| {
+                if let Some(mutable_file) = mutable_file.as_mut() {
+                    mutable_file.last_charged_at = now;
+
+                    if mutable_file.is_delinquent() {
+                        mutable_file.status = FileStatus::Active;
+                        Self::deposit_event(Event::FileReinstated {
+                            file: file.clone(),
+                        });
+                    }
+                }
+            });
 
             Self::deposit_event(Event::StorageFeePaid {
                 file: file.clone(),
                 amount: total_fee,
             });
 
-            if spread {
-                let divider =
+            let divider =
                 <BalanceCurrencyTypeOf<T> as sp_runtime::traits::SaturatedConversion>::saturated_from(2u32);
-                let half_fee =
-                    sp_runtime::traits::CheckedDiv::checked_div(&total_fee, &divider).unwrap();
+            let half_fee =
+                sp_runtime::traits::CheckedDiv::checked_div(&total_fee, &divider).unwrap();
 
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&big_brother, half_fee);
-                Self::deposit_event(Event::StorageFeeDistributed {
-                    file: file.clone(),
-                    to: big_brother.clone(),
-                    amount: half_fee,
-                });
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&servicer, half_fee);
-                Self::deposit_event(Event::StorageFeeDistributed {
-                    file: file.clone(),
-                    to: servicer,
-                    amount: half_fee,
-                });
-                ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(big_brother, attester)?;
-            } else {
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&big_brother, total_fee);
+            let amount = Self::split_fee_to(&mut fee, &big_brother, half_fee);
+            Self::deposit_event(Event::StorageFeeDistributed {
+                file: file.clone(),
+                to: big_brother.clone(),
+                amount,
+            });
+            for (to, amount) in Self::split_fee_to_servicer(&mut fee, servicer, half_fee) {
                 Self::deposit_event(Event::StorageFeeDistributed {
                     file: file.clone(),
-                    to: big_brother,
-                    amount: total_fee,
+                    to,
+                    amount,
                 });
             }
+            Self::settle_fee_remainder(fee);
+
+            // only the replica which proved keeping the file earns reputation
+            if !ngr_svrg::Pallet::<T>::is_attester_revoked(attester) {
+                ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(big_brother, *attester)?;
+            }
 
             Ok(())
         }
 
+        /// sell a file to `beneficiary` at `transfer_fee`, an `escrowed` price
+        /// is unreserved on the beneficiary first
         fn ownership_transfer(
             file: &T::AccountId,
             beneficiary: T::AccountId,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            escrowed: bool,
         ) -> Result<(), sp_runtime::DispatchError> {
             if !<Files<T>>::contains_key(file) {
                 return Err(<Error<T>>::FileNotFound.into());
             }
 
-            let FileInformation {
-                uploader,
-                transfer_fee,
-                big_brother,
-                owner,
-                ..
-            } = Self::files(file).unwrap();
+            let file_info = Self::files(file).unwrap();
+            let owner = file_info.owner.clone();
+            ensure!(!owner.eq(&beneficiary), <Error<T>>::CannotBuyOwnFile);
+
+            let asset = file_info.pricing_asset;
+            let existence_requirement = if escrowed {
+                Self::unreserve_in(asset, &beneficiary, transfer_fee);
+                frame_support::traits::tokens::ExistenceRequirement::AllowDeath
+            } else {
+                frame_support::traits::tokens::ExistenceRequirement::KeepAlive
+            };
 
-            let withdraw_reason = frame_support::traits::tokens::WithdrawReasons::FEE;
-            let divider =
-                <BalanceCurrencyTypeOf<T> as sp_runtime::traits::SaturatedConversion>::saturated_from(2u32);
             let royalty_part = T::RoyaltyFeeDistribution::get();
             let royalty_part_amount = royalty_part.mul_floor(transfer_fee);
             let owner_part_amount = transfer_fee - royalty_part_amount;
-            let half_royalty =
-                sp_runtime::traits::CheckedDiv::checked_div(&royalty_part_amount, &divider)
-                    .unwrap();
 
             <Files<T>>::try_mutate(file, |mutable_file| {
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::withdraw(
-                    &beneficiary,
-                    transfer_fee,
-                    withdraw_reason,
-                    frame_support::traits::tokens::ExistenceRequirement::KeepAlive,
-                )?;
+                let mut fee =
+                    Self::withdraw_fee(asset, &beneficiary, transfer_fee, existence_requirement)?;
 
                 Self::deposit_event(Event::OwnershipTransferFeePaid {
                     by: beneficiary.clone(),
                     file: file.clone(),
                     amount: transfer_fee,
                 });
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&uploader, half_royalty);
-                Self::deposit_event(Event::OwnershipTransferFeeDistributed {
-                    file: file.clone(),
-                    to: uploader,
-                    amount: half_royalty,
-                });
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&big_brother, half_royalty);
-                Self::deposit_event(Event::OwnershipTransferFeeDistributed {
-                    file: file.clone(),
-                    to: big_brother,
-                    amount: half_royalty,
-                });
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                    T::AccountId,
-                >>::deposit_creating(&owner, owner_part_amount);
+                for (to, amount) in
+                    Self::split_fee_to_royalties(&mut fee, &file_info, royalty_part_amount)
+                {
+                    Self::deposit_event(Event::OwnershipTransferFeeDistributed {
+                        file: file.clone(),
+                        to,
+                        amount,
+                    });
+                }
+                let amount = Self::split_fee_to(&mut fee, &owner, owner_part_amount);
                 Self::deposit_event(Event::OwnershipTransferFeeDistributed {
                     file: file.clone(),
                     to: owner.clone(),
-                    amount: owner_part_amount,
+                    amount,
                 });
+                Self::settle_fee_remainder(fee);
                 let mutable_file = mutable_file.as_mut().unwrap();
                 mutable_file.owner = beneficiary.clone();
                 Self::deposit_event(Event::FileOwnershipTransferred {
@@ -387,56 +2520,491 @@ pub mod pallet {
                 Result::<(), sp_runtime::DispatchError>::Ok(())
             })?;
 
+            Self::unindex_file_of_owner(&owner, file);
+            Self::try_index_file_of_owner(&beneficiary, file)?;
+
+            // the new owner sets its own terms, its offer (if any) is refunded
+            Self::apply_due_pricing_update(file);
+            <PendingPricing<T>>::remove(file);
+            <Listings<T>>::remove(file);
+            if let Some(amount) = Self::take_offer(file, &beneficiary) {
+                Self::unreserve_in(asset, &beneficiary, amount);
+            }
+
+            Ok(())
+        }
+
+        /// list a file at its current transfer fee, a different price is
+        /// scheduled as a pricing update, buyers keep the current price until
+        /// the notice period is over
+        fn list_file(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+            price: BalanceCurrencyTypeOf<T>,
+        ) -> Result<sp_std::vec::Vec<Event<T>>, sp_runtime::DispatchError> {
+            Self::apply_due_pricing_update(file);
+            let file_info = Self::ensure_and_get_file_of_owner(file, owner)?;
+            ensure!(
+                !sp_runtime::traits::Zero::is_zero(&price),
+                <Error<T>>::OwnershipTransferFeeMustNotZero,
+            );
+            ensure!(
+                Self::auction_end_of(file).is_none(),
+                <Error<T>>::AuctionInProgress
+            );
+            <Listings<T>>::insert(
+                file,
+                Listing::FixedPrice {
+                    price: file_info.transfer_fee,
+                },
+            );
+            let mut events = sp_std::vec![Event::FileListed {
+                file: file.clone(),
+                price: file_info.transfer_fee,
+            }];
+
+            if price.ne(&file_info.transfer_fee) {
+                let download_fee = Self::pending_pricing(file)
+                    .map_or(file_info.download_fee, |update| update.download_fee);
+                events.push(Self::schedule_pricing_update(
+                    file,
+                    owner,
+                    price,
+                    download_fee,
+                )?);
+            }
+
+            Ok(events)
+        }
+
+        fn unlist_file(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            Self::ensure_and_get_file_of_owner(file, owner)?;
+            ensure!(
+                <Listings<T>>::contains_key(file),
+                <Error<T>>::FileNotForSale
+            );
+            ensure!(
+                !<HighestBids<T>>::contains_key(file),
+                <Error<T>>::AuctionInProgress
+            );
+            <Listings<T>>::remove(file);
+
+            Ok(Event::FileUnlisted {
+                file: file.clone(),
+            })
+        }
+
+        fn buy_listed_file(
+            file: &T::AccountId,
+            buyer: T::AccountId,
+            max_price: BalanceCurrencyTypeOf<T>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            Self::apply_due_pricing_update(file);
+            let price = match Self::listings(file) {
+                | Some(Listing::FixedPrice {
+                    price,
+                }) => price,
+                | _ => return Err(<Error<T>>::FileNotForSale.into()),
+            };
+            ensure!(price <= max_price, <Error<T>>::PriceAboveLimit);
+
+            Self::ownership_transfer(file, buyer, price, false)
+        }
+
+        fn start_auction(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+            reserve_price: BalanceCurrencyTypeOf<T>,
+            duration: BlockNumber<T>,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            Self::ensure_and_get_file_of_owner(file, owner)?;
+            ensure!(
+                !sp_runtime::traits::Zero::is_zero(&reserve_price),
+                <Error<T>>::OwnershipTransferFeeMustNotZero,
+            );
+            ensure!(
+                !sp_runtime::traits::Zero::is_zero(&duration)
+                    && duration <= T::MaxAuctionDuration::get(),
+                <Error<T>>::InvalidAuctionDuration,
+            );
+            ensure!(
+                Self::auction_end_of(file).is_none(),
+                <Error<T>>::AuctionInProgress
+            );
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let end = sp_runtime::traits::Saturating::saturating_add(now, duration);
+            <Listings<T>>::insert(
+                file,
+                Listing::Auction {
+                    reserve_price,
+                    end,
+                },
+            );
+
+            Ok(Event::AuctionStarted {
+                file: file.clone(),
+                reserve_price,
+                end,
+            })
+        }
+
+        fn place_bid(
+            file: &T::AccountId,
+            bidder: T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            ensure!(!file_info.owner.eq(&bidder), <Error<T>>::CannotBuyOwnFile);
+
+            let (reserve_price, end) = match Self::listings(file) {
+                | Some(Listing::Auction {
+                    reserve_price,
+                    end,
+                }) => (reserve_price, end),
+                | _ => return Err(<Error<T>>::AuctionNotFound.into()),
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now < end, <Error<T>>::AuctionEnded);
+            ensure!(amount >= reserve_price, <Error<T>>::BidTooLow);
+
+            if let Some(highest) = Self::highest_bids(file) {
+                ensure!(amount > highest.amount, <Error<T>>::BidTooLow);
+                Self::unreserve_in(file_info.pricing_asset, &highest.bidder, highest.amount);
+            }
+            Self::reserve_in(file_info.pricing_asset, &bidder, amount)?;
+            <HighestBids<T>>::insert(
+                file,
+                Bid {
+                    bidder: bidder.clone(),
+                    amount,
+                },
+            );
+
+            Ok(Event::BidPlaced {
+                file: file.clone(),
+                who: bidder,
+                amount,
+            })
+        }
+
+        fn settle_auction(file: &T::AccountId) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let end = Self::auction_end_of(file).ok_or(<Error<T>>::AuctionNotFound)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now >= end, <Error<T>>::AuctionNotEnded);
+
+            <Listings<T>>::remove(file);
+            let winner = <HighestBids<T>>::take(file).map(|bid| (bid.bidder, bid.amount));
+
+            if let Some((bidder, amount)) = winner.clone() {
+                // the winner may not take the file anymore (e.g. owning too many files), the
+                // auction is closed without a sale and the bid refunded
+                let transferred = frame_support::storage::with_storage_layer(|| {
+                    Self::ownership_transfer(file, bidder.clone(), amount, true)
+                });
+                if let Err(error) = transferred {
+                    let asset = Self::files(file).and_then(|file_info| file_info.pricing_asset);
+                    Self::unreserve_in(asset, &bidder, amount);
+
+                    return Ok(Event::AuctionSettlementFailed {
+                        file: file.clone(),
+                        bidder,
+                        amount,
+                        error,
+                    });
+                }
+            }
+
+            Ok(Event::AuctionSettled {
+                file: file.clone(),
+                winner,
+            })
+        }
+
+        fn make_offer(
+            file: &T::AccountId,
+            buyer: T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            ensure!(!file_info.owner.eq(&buyer), <Error<T>>::CannotBuyOwnFile);
+            ensure!(
+                !sp_runtime::traits::Zero::is_zero(&amount),
+                <Error<T>>::OfferMustNotZero
+            );
+
+            match Self::offers(file, &buyer) {
+                | Some(previous) => Self::unreserve_in(file_info.pricing_asset, &buyer, previous),
+                | None => {
+                    <OfferCounts<T>>::try_mutate(file, |count| {
+                        ensure!(
+                            *count < T::MaxOffersPerFile::get(),
+                            <Error<T>>::TooManyOffers
+                        );
+                        *count = count.saturating_add(1);
+
+                        Result::<(), sp_runtime::DispatchError>::Ok(())
+                    })?;
+                },
+            }
+            Self::reserve_in(file_info.pricing_asset, &buyer, amount)?;
+            <Offers<T>>::insert(file, &buyer, amount);
+
+            Ok(Event::OfferMade {
+                file: file.clone(),
+                who: buyer,
+                amount,
+            })
+        }
+
+        fn withdraw_offer(
+            file: &T::AccountId,
+            buyer: T::AccountId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let file_info = Self::files(file).ok_or(<Error<T>>::FileNotFound)?;
+            let amount = Self::take_offer(file, &buyer).ok_or(<Error<T>>::OfferNotFound)?;
+            Self::unreserve_in(file_info.pricing_asset, &buyer, amount);
+
+            Ok(Event::OfferWithdrawn {
+                file: file.clone(),
+                who: buyer,
+                amount,
+            })
+        }
+
+        fn accept_offer(
+            file: &T::AccountId,
+            owner: &T::AccountId,
+            buyer: T::AccountId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            Self::ensure_and_get_file_of_owner(file, owner)?;
+            ensure!(
+                Self::auction_end_of(file).is_none(),
+                <Error<T>>::AuctionInProgress
+            );
+
+            let amount = Self::take_offer(file, &buyer).ok_or(<Error<T>>::OfferNotFound)?;
+            Self::ownership_transfer(file, buyer.clone(), amount, true)?;
+
+            Ok(Event::OfferAccepted {
+                file: file.clone(),
+                who: buyer,
+                amount,
+            })
+        }
+
+        /// ensure an attester may keep a new replica next to the `existing`
+        /// ones
+        fn ensure_replica_is_available(
+            existing: &[FileReplica<T::AccountId>],
+            servicer: &T::AccountId,
+            attester: &AttesterId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(
+                !ngr_svrg::Pallet::<T>::is_attester_revoked(attester),
+                <Error<T>>::AttesterRevoked,
+            );
+            ensure!(
+                ngr_svrg::Pallet::<T>::is_attester_active(attester),
+                <Error<T>>::AttesterInactive,
+            );
+            ensure!(
+                ngr_svrg::Pallet::<T>::attesters(attester)
+                    .and_then(|device| device.binder)
+                    .is_some_and(|binder| binder.eq(servicer)),
+                <Error<T>>::ReplicaAttesterNotBinded,
+            );
+            ensure!(
+                !existing.iter().any(|replica| {
+                    replica.servicer.eq(servicer) || replica.attester.eq(attester)
+                }),
+                <Error<T>>::DuplicateReplica,
+            );
+
             Ok(())
         }
 
+        fn try_add_replica(
+            file: &T::AccountId,
+            servicer: T::AccountId,
+            attester: AttesterId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            <Files<T>>::try_mutate(file, |mutable_file| {
+                let mutable_file = mutable_file.as_mut().ok_or(<Error<T>>::FileNotFound)?;
+                ensure!(
+                    mutable_file.is_under_replicated(),
+                    <Error<T>>::TooManyReplicas,
+                );
+                Self::ensure_replica_is_available(&mutable_file.replicas, &servicer, &attester)?;
+                mutable_file
+                    .replicas
+                    .try_push(FileReplica {
+                        servicer: servicer.clone(),
+                        attester,
+                        attester_revoked: false,
+                    })
+                    .map_err(|_| <Error<T>>::TooManyReplicas)?;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            <FilesByAttester<T>>::insert(attester, file, ());
+
+            Ok(Event::ReplicaAdded {
+                file: file.clone(),
+                servicer,
+                attester,
+            })
+        }
+
+        fn try_replace_replica(
+            file: &T::AccountId,
+            old_attester: AttesterId,
+            servicer: T::AccountId,
+            attester: AttesterId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let old_replica = <Files<T>>::try_mutate(file, |mutable_file| {
+                let mutable_file = mutable_file.as_mut().ok_or(<Error<T>>::FileNotFound)?;
+                let others = mutable_file
+                    .replicas
+                    .iter()
+                    .filter(|replica| !replica.attester.eq(&old_attester))
+                    .cloned()
+                    .collect::<sp_std::vec::Vec<_>>();
+                Self::ensure_replica_is_available(&others, &servicer, &attester)?;
+                let replica = mutable_file
+                    .replicas
+                    .iter_mut()
+                    .find(|replica| replica.attester.eq(&old_attester))
+                    .ok_or(<Error<T>>::ReplicaNotFound)?;
+
+                Result::<_, sp_runtime::DispatchError>::Ok(sp_std::mem::replace(
+                    replica,
+                    FileReplica {
+                        servicer: servicer.clone(),
+                        attester,
+                        attester_revoked: false,
+                    },
+                ))
+            })?;
+            <FilesByAttester<T>>::remove(old_attester, file);
+            <FilesByAttester<T>>::insert(attester, file, ());
+
+            // pending challenge of the replaced replica is void, re-issued next sweep
+            if Self::challenges(file).is_some_and(|challenge| challenge.attester.eq(&old_attester))
+            {
+                <Challenges<T>>::remove(file);
+            }
+
+            Ok(Event::ReplicaReplaced {
+                file: file.clone(),
+                from: (old_replica.servicer, old_replica.attester),
+                to: (servicer, attester),
+            })
+        }
+
         fn upload_file(
             file: T::AccountId,
             args: FileInformationArgs<T::AccountId, BalanceCurrencyTypeOf<T>>,
+            pricing_asset: Option<AssetIdOf<T>>,
         ) -> Result<(), sp_runtime::DispatchError> {
+            // an untranslated file at the same key would be overwritten
+            ensure!(
+                !<FileLayoutMigrationCursor<T>>::exists(),
+                <Error<T>>::FileMigrationInProgress,
+            );
+
             if <Files<T>>::contains_key(&file) {
                 return Err(<Error<T>>::FileAlreadyExist.into());
             }
 
+            // stale licenses & access list entries would be inherited
+            ensure!(
+                !<DeletedFiles<T>>::contains_key(&file),
+                <Error<T>>::FileEntriesPendingRemoval,
+            );
+
             if <Hashes<T>>::contains_key(args.hash) {
                 return Err(<Error<T>>::FileAlreadyExist.into());
             }
 
-            let zero = <BalanceCurrencyTypeOf<T> as sp_runtime::traits::SaturatedConversion>::saturated_from(0u32);
+            if let Some(asset) = pricing_asset {
+                ensure!(
+                    Self::accepted_assets(asset).is_some_and(|accepted| !accepted.retired),
+                    <Error<T>>::AssetNotAccepted,
+                );
+            }
+            Self::ensure_valid_pricing(
+                pricing_asset,
+                args.size,
+                args.transfer_fee,
+                args.download_fee,
+            )?;
+
+            ensure!(!args.replicas.is_empty(), <Error<T>>::NoReplicas);
+            ensure!(
+                args.replication_factor <= T::MaxReplicas::get(),
+                <Error<T>>::InvalidReplicationFactor,
+            );
+            ensure!(
+                args.replicas.len() as u32 <= args.replication_factor,
+                <Error<T>>::TooManyReplicas,
+            );
 
-            if args.transfer_fee == zero {
-                return Err(<Error<T>>::OwnershipTransferFeeMustNotZero.into());
+            let mut replicas = BoundedVec::<FileReplica<T::AccountId>, T::MaxReplicas>::default();
+
+            for (servicer, attester) in &args.replicas {
+                Self::ensure_replica_is_available(&replicas, servicer, attester)?;
+                replicas
+                    .try_push(FileReplica {
+                        servicer: servicer.clone(),
+                        attester: *attester,
+                        attester_revoked: false,
+                    })
+                    .map_err(|_| <Error<T>>::TooManyReplicas)?;
             }
 
+            let royalties = Self::build_royalties(&args)?;
+
+            ensure!(
+                args.chunk_size > 0 && args.chunk_size <= T::MaxChunkSize::get(),
+                <Error<T>>::InvalidChunkSize,
+            );
+            let chunk_count = ngr_chmk::chunk_count(args.size, args.chunk_size);
+            ensure!(
+                ngr_chmk::proof_depth(chunk_count) <= T::MaxProofDepth::get(),
+                <Error<T>>::TooManyChunks,
+            );
+
             let file_info = FileInformation {
                 hash: args.hash,
                 uploader: args.uploader.clone(),
                 big_brother: args.big_brother.clone(),
-                servicer: args.servicer.clone(),
-                attester: args.attester.clone(),
                 owner: args.uploader.clone(),
                 transfer_fee: args.transfer_fee,
                 download_fee: args.download_fee,
                 size: args.size,
+                chunk_size: args.chunk_size,
+                chunk_root: args.chunk_root,
+                pricing_asset,
+                replication_factor: args.replication_factor,
+                replicas: replicas.clone(),
+                royalties,
+                last_charged_at: <frame_system::Pallet<T>>::block_number(),
+                status: FileStatus::Active,
             };
 
-            if let Some(download_fee) = file_info.download_fee.as_ref() {
-                let min_download_fee = T::MinDownloadFeePerByte::bytes_to_fee(args.size);
-
-                if (*download_fee) < min_download_fee {
-                    return Err(<Error<T>>::DownloadFeeTooLow.into());
-                }
-            }
-
-            let withdraw_reason = frame_support::traits::tokens::WithdrawReasons::FEE;
-            let total_fee = T::UploadFeePerByte::bytes_to_fee(args.size);
+            let native_fee = T::UploadFeePerByte::bytes_to_fee(args.size);
+            let total_fee = Self::protocol_fee_in(pricing_asset, native_fee)?;
             let servicer_part = T::ServicerUploadFeeDistribution::get();
             let servicer_part_amount = servicer_part.mul_floor(total_fee);
             let bb_part_amount = total_fee - servicer_part_amount;
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::withdraw(
+            let mut fee = Self::withdraw_fee(
+                pricing_asset,
                 &args.uploader,
                 total_fee,
-                withdraw_reason,
                 frame_support::traits::tokens::ExistenceRequirement::KeepAlive,
             )?;
             Self::deposit_event(Event::UploadFeePaid {
@@ -447,26 +3015,46 @@ pub mod pallet {
 
             <Files<T>>::insert(file.clone(), file_info);
             <Hashes<T>>::insert(args.hash, file.clone());
+            Self::try_index_file_of_owner(&args.uploader, &file)?;
+            Self::try_index_file_of_uploader(&args.uploader, &file)?;
+            <Listings<T>>::insert(
+                file.clone(),
+                Listing::FixedPrice {
+                    price: args.transfer_fee,
+                },
+            );
+            for replica in replicas.iter() {
+                <FilesByAttester<T>>::insert(replica.attester, file.clone(), ());
+            }
 
             Self::deposit_event(Event::FileUploaded {
                 file: file.clone(),
                 length: args.size,
             });
 
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::deposit_creating(&args.big_brother, bb_part_amount);
+            let amount = Self::split_fee_to(&mut fee, &args.big_brother, bb_part_amount);
             Self::deposit_event(Event::UploadFeeDistributed {
                 file: file.clone(),
                 to: args.big_brother.clone(),
-                amount: bb_part_amount,
-            });
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::deposit_creating(&args.servicer, servicer_part_amount);
-            Self::deposit_event(Event::UploadFeeDistributed {
-                file,
-                to: args.servicer,
-                amount: servicer_part_amount,
+                amount,
             });
+            for (to, amount) in
+                Self::split_fee_to_replicas(&mut fee, &replicas, servicer_part_amount)
+            {
+                Self::deposit_event(Event::UploadFeeDistributed {
+                    file: file.clone(),
+                    to,
+                    amount,
+                });
+            }
+            Self::settle_fee_remainder(fee);
 
-            ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(args.big_brother, args.attester)?;
+            for replica in replicas.iter() {
+                ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(
+                    args.big_brother.clone(),
+                    replica.attester,
+                )?;
+            }
 
             Ok(())
         }
@@ -479,36 +3067,44 @@ pub mod pallet {
                 return Err(<Error<T>>::FileNotFound.into());
             }
 
+            Self::apply_due_pricing_update(file);
             let the_file = Self::files(file).unwrap();
+            ensure!(!the_file.is_delinquent(), <Error<T>>::FileDelinquent);
+            ensure!(
+                Self::has_access(file, &the_file, downloader),
+                <Error<T>>::AccessDenied,
+            );
 
-            if the_file.is_ffa() {
+            if the_file.is_ffa() || Self::access_mode_of(file, &the_file) == AccessMode::Public {
                 return Ok(());
             }
 
-            let FileInformation {
-                big_brother,
-                servicer,
-                owner,
-                download_fee,
-                attester,
-                ..
-            } = the_file;
+            if let Some(mut license) = Self::active_license(file, downloader) {
+                license.downloads = license.downloads.saturating_add(1);
+                <Licenses<T>>::insert(file, downloader, license);
+                Self::deposit_event(Event::LicensedDownload {
+                    file: file.clone(),
+                    who: downloader.clone(),
+                    downloads: license.downloads,
+                });
+
+                return Ok(());
+            }
 
-            let download_fee = download_fee.unwrap();
-            let withdraw_reason = frame_support::traits::tokens::WithdrawReasons::FEE;
+            let download_fee = the_file.download_fee.unwrap();
             let divider =
                 <BalanceCurrencyTypeOf<T> as sp_runtime::traits::SaturatedConversion>::saturated_from(2u32);
             let bb_part = T::BigBrotherDownloadFeeDistribution::get();
             let bb_part_amount = bb_part.mul_floor(download_fee);
-            let owner_part_amount = download_fee - bb_part_amount;
+            let proceeds_amount = download_fee - bb_part_amount;
+            let royalty_part_amount = T::RoyaltyFeeDistribution::get().mul_floor(proceeds_amount);
+            let owner_part_amount = proceeds_amount - royalty_part_amount;
             let half_bb_fee =
                 sp_runtime::traits::CheckedDiv::checked_div(&bb_part_amount, &divider).unwrap();
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<
-                T::AccountId,
-            >>::withdraw(
+            let mut fee = Self::withdraw_fee(
+                the_file.pricing_asset,
                 downloader,
                 download_fee,
-                withdraw_reason,
                 frame_support::traits::tokens::ExistenceRequirement::KeepAlive,
             )?;
             Self::deposit_event(Event::DownloadFeePaid {
@@ -516,28 +3112,48 @@ pub mod pallet {
                 file: file.clone(),
                 amount: download_fee,
             });
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::deposit_creating(&owner, owner_part_amount);
-            Self::deposit_event(Event::DownloadFeeDistributed {
-                file: file.clone(),
-                to: owner,
-                amount: owner_part_amount,
-            });
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::deposit_creating(&big_brother, half_bb_fee);
+            Self::issue_license(file, downloader);
+            for (to, amount) in
+                Self::split_fee_to_royalties(&mut fee, &the_file, royalty_part_amount)
+            {
+                Self::deposit_event(Event::DownloadFeeDistributed {
+                    file: file.clone(),
+                    to,
+                    amount,
+                });
+            }
+            let amount = Self::split_fee_to(&mut fee, &the_file.owner, owner_part_amount);
             Self::deposit_event(Event::DownloadFeeDistributed {
                 file: file.clone(),
-                to: big_brother.clone(),
-                amount: half_bb_fee,
+                to: the_file.owner.clone(),
+                amount,
             });
-            let _ = <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::deposit_creating(&servicer, half_bb_fee);
+            let amount = Self::split_fee_to(&mut fee, &the_file.big_brother, half_bb_fee);
             Self::deposit_event(Event::DownloadFeeDistributed {
                 file: file.clone(),
-                to: servicer,
-                amount: half_bb_fee,
+                to: the_file.big_brother.clone(),
+                amount,
             });
+            for (to, amount) in
+                Self::split_fee_to_replicas(&mut fee, &the_file.replicas, half_bb_fee)
+            {
+                Self::deposit_event(Event::DownloadFeeDistributed {
+                    file: file.clone(),
+                    to,
+                    amount,
+                });
+            }
+            Self::settle_fee_remainder(fee);
 
-            ngr_svrg::Pallet::<T>::rep_increase_by_attester_id(big_brother, attester)?;
+            // files of revoked attesters stay downloadable, so replicas can be replaced
+            Self::rep_increase_replicas(&the_file.big_brother, &the_file.replicas)
+        }
+    }
 
-            Ok(())
+    impl<T: Config> ngr_svrg::OnAttesterRevoked for Pallet<T> {
+        /// the replicas are flagged on idle, an attester may keep many files
+        fn on_attester_revoked(attester_id: &AttesterId) {
+            <PendingReplicaRevocations<T>>::insert(attester_id, ());
         }
     }
 
@@ -548,16 +3164,20 @@ pub mod pallet {
     #[pallet::call]
     /// Dispatchable functions.
     impl<T: Config> Pallet<T> {
-        /// Servicer: upload a file
+        /// Servicer: upload a file to a caller-supplied file account
+        ///
+        /// Deprecated: the file account may collide with a user account, use
+        /// `servicer_upload_derived` instead
         #[pallet::call_index(0)]
         #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
         pub fn servicer_upload(
             origin: OriginFor<T>,
             file: T::AccountId,
             args: FileInformationArgs<T::AccountId, BalanceCurrencyTypeOf<T>>,
+            pricing_asset: Option<AssetIdOf<T>>,
         ) -> DispatchResultWithPostInfo {
             ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
-            Self::upload_file(file, args)?;
+            Self::upload_file(file, args, pricing_asset)?;
 
             Ok(Pays::No.into())
         }
@@ -576,32 +3196,436 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
-        /// Servicer: take storage fee
+        /// Servicer: take storage fee, issues a storage challenge now instead
+        /// of waiting for the automatic one, fee is released on a valid
+        /// answer
         #[pallet::call_index(2)]
         #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
         pub fn servicer_take_storage_fee(
             origin: OriginFor<T>,
             file: T::AccountId,
-            spread: bool,
         ) -> DispatchResultWithPostInfo {
             ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
-            Self::distribute_storage_fee(&file, spread)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::try_issue_challenge(&file, now)?;
 
             Ok(Pays::No.into())
         }
 
-        /// Any: buy file
+        /// Any: buy a file listed at a fixed price, up to `max_price`
         #[pallet::call_index(3)]
         #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
         pub fn any_buy_file(
             origin: OriginFor<T>,
             file: T::AccountId,
+            max_price: BalanceCurrencyTypeOf<T>,
         ) -> DispatchResultWithPostInfo {
             let beneficiary = ensure_signed(origin)?;
-            Self::ownership_transfer(&file, beneficiary)?;
+            Self::buy_listed_file(&file, beneficiary, max_price)?;
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Any: reinstate a delinquent file after its account is topped up
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn any_reinstate_file(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+            let file_info = Self::files(&file).ok_or(<Error<T>>::FileNotFound)?;
+            ensure!(file_info.is_delinquent(), <Error<T>>::FileNotDelinquent);
+            ensure!(
+                Self::can_cover_storage_fee(&file, &file_info),
+                <Error<T>>::FileDelinquent,
+            );
+            <Files<T>>::mutate(&file, |mutable_file| {
+                if let Some(mutable_file) = mutable_file.as_mut() {
+                    mutable_file.status = FileStatus::Active; // fee is taken on
+                                                              // next challenge
+                }
+            });
+            Self::deposit_event(Event::FileReinstated {
+                file,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: delete a file, the file account balance is refunded minus the
+        /// early termination fee
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_delete_file(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::owner_delete(&file, owner)?;
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: set the file access mode
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_set_access_mode(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            mode: AccessMode,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_and_get_file_of_owner(&file, &owner)?;
+            <AccessModes<T>>::insert(&file, mode);
+            Self::deposit_event(Event::AccessModeChanged {
+                file,
+                mode,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: put an account on the file access list (grant on allowlist,
+        /// revoke on denylist), optionally until a block
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_access_list_set(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            who: T::AccountId,
+            expires_at: Option<BlockNumber<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_and_get_file_of_owner(&file, &owner)?;
+            Self::try_set_access_list_entry(&file, &who, expires_at)?;
+            Self::deposit_event(Event::AccessListEntrySet {
+                file,
+                who,
+                expires_at,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: remove an account from the file access list
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_access_list_remove(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_and_get_file_of_owner(&file, &owner)?;
+            Self::try_remove_access_list_entry(&file, &who)?;
+            Self::deposit_event(Event::AccessListEntryRemoved {
+                file,
+                who,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Servicer: answer a storage challenge with the challenged chunk and
+        /// its inclusion proof
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn servicer_answer_challenge(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            chunk: BoundedVec<u8, T::MaxChunkSize>,
+            proof: BoundedVec<FileHash, T::MaxProofDepth>,
+        ) -> DispatchResultWithPostInfo {
+            let servicer = ensure_signed(origin)?;
+            Self::try_answer_challenge(servicer, &file, &chunk, &proof)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Servicer: add a replica to an under-replicated file
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn servicer_replica_add(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            servicer: T::AccountId,
+            attester: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            let event = Self::try_add_replica(&file, servicer, attester)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Servicer: replace a replica of a file (e.g. when it goes offline)
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn servicer_replica_replace(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            old_attester: AttesterId,
+            servicer: T::AccountId,
+            attester: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            let event = Self::try_replace_replica(&file, old_attester, servicer, attester)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Owner: set the file replication factor
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_set_replication_factor(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            replication_factor: u32,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let file_info = Self::ensure_and_get_file_of_owner(&file, &owner)?;
+            ensure!(
+                replication_factor >= file_info.replicas.len() as u32
+                    && replication_factor <= T::MaxReplicas::get(),
+                <Error<T>>::InvalidReplicationFactor,
+            );
+            <Files<T>>::mutate(&file, |mutable_file| {
+                if let Some(mutable_file) = mutable_file.as_mut() {
+                    mutable_file.replication_factor = replication_factor;
+                }
+            });
+            Self::deposit_event(Event::ReplicationFactorChanged {
+                file,
+                replication_factor,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: list a file for sale at a fixed price, the transfer fee. A
+        /// different price takes effect after the pricing notice period, as
+        /// `owner_update_pricing`
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_list_file(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            price: BalanceCurrencyTypeOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let events = Self::list_file(&file, &owner, price)?;
+
+            for event in events {
+                Self::deposit_event(event);
+            }
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: mark a file not for sale, an auction without bids is
+        /// cancelled
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_unlist_file(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let event = Self::unlist_file(&file, &owner)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: put a file on an English auction for `duration` blocks
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_start_auction(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            reserve_price: BalanceCurrencyTypeOf<T>,
+            duration: BlockNumber<T>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let event = Self::start_auction(&file, &owner, reserve_price, duration)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Any: bid on a file auction, the amount is reserved until outbid
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn any_bid(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let bidder = ensure_signed(origin)?;
+            let event = Self::place_bid(&file, bidder, amount)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Any: settle an ended auction, the file is sold to the highest bidder
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn any_settle_auction(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+            let event = Self::settle_auction(&file)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Any: make (or change) an offer on a file, the amount is reserved
+        /// until withdrawn or accepted
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn any_make_offer(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let buyer = ensure_signed(origin)?;
+            let event = Self::make_offer(&file, buyer, amount)?;
+            Self::deposit_event(event);
 
             Ok(Pays::Yes.into())
         }
+
+        /// Any: withdraw an offer on a file
+        #[pallet::call_index(19)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn any_withdraw_offer(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let buyer = ensure_signed(origin)?;
+            let event = Self::withdraw_offer(&file, buyer)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: accept an offer, the file is sold at the offered amount
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_accept_offer(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            buyer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let event = Self::accept_offer(&file, &owner, buyer)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Owner: update the transfer & download fee (`None` makes the file
+        /// FFA), takes effect after the pricing notice period
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn owner_update_pricing(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            transfer_fee: BalanceCurrencyTypeOf<T>,
+            download_fee: Option<BalanceCurrencyTypeOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let event = Self::schedule_pricing_update(&file, &owner, transfer_fee, download_fee)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Council: accept an asset for file pricing (or update its rate)
+        #[pallet::call_index(22)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn council_asset_accept(
+            origin: OriginFor<T>,
+            asset: AssetIdOf<T>,
+            rate: sp_runtime::FixedU128,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            <AcceptedAssets<T>>::insert(
+                asset,
+                AcceptedAsset {
+                    rate,
+                    retired: false,
+                },
+            );
+            Self::deposit_event(Event::AssetAccepted {
+                asset,
+                rate,
+            });
+
+            Ok(Pays::No.into())
+        }
+
+        /// Council: retire an asset, files already priced in it keep paying at
+        /// the last rate
+        #[pallet::call_index(23)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn council_asset_retire(
+            origin: OriginFor<T>,
+            asset: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            <AcceptedAssets<T>>::try_mutate(asset, |accepted_asset| {
+                let accepted_asset = accepted_asset
+                    .as_mut()
+                    .ok_or(<Error<T>>::AssetNotAccepted)?;
+                accepted_asset.retired = true;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            Self::deposit_event(Event::AssetRetired {
+                asset,
+            });
+
+            Ok(Pays::No.into())
+        }
+
+        /// Servicer: upload a file, its account is derived from the content
+        /// hash & uploader (see `file_account_of`)
+        #[pallet::call_index(24)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn servicer_upload_derived(
+            origin: OriginFor<T>,
+            args: FileInformationArgs<T::AccountId, BalanceCurrencyTypeOf<T>>,
+            pricing_asset: Option<AssetIdOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            let file = Self::file_account_of(&args.hash, &args.uploader);
+            Self::upload_file(file, args, pricing_asset)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Servicer: set the chunk manifest of a file uploaded before chunk
+        /// manifests, it can be challenged from then on
+        #[pallet::call_index(25)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn servicer_set_chunk_manifest(
+            origin: OriginFor<T>,
+            file: T::AccountId,
+            chunk_size: u32,
+            chunk_root: FileHash,
+        ) -> DispatchResultWithPostInfo {
+            ngr_bbcm::Pallet::<T>::ensure_council_member_or_root(origin)?;
+            let event = Self::set_chunk_manifest(&file, chunk_size, chunk_root)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::No.into())
+        }
     }
 
     // endregion
@@ -0,0 +1,175 @@
+//! Storage migrations for the PDA Files pallet
+
+pub mod v1 {
+    use crate::*;
+    use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use frame_support::weights::Weight;
+
+    mod old {
+        use crate::*;
+        use frame_support::pallet_prelude::OptionQuery;
+
+        /// Files before v1, same prefix as the current map
+        #[frame_support::storage_alias]
+        pub type Files<T: Config> = StorageMap<
+            Pallet<T>,
+            frame_support::Blake2_128Concat,
+            AccountTypeOf<T>,
+            super::OldFileInformation<AccountTypeOf<T>, BalanceCurrencyTypeOf<T>>,
+            OptionQuery,
+        >;
+    }
+
+    /// File Information before v1 (single servicer, no chunk manifest)
+    #[derive(codec::Decode, codec::Encode)]
+    pub struct OldFileInformation<AccountId, Balance> {
+        hash: FileHash,
+        uploader: AccountId,
+        big_brother: AccountId,
+        servicer: AccountId,
+        attester: AttesterId,
+        owner: AccountId,
+        transfer_fee: Balance,
+        download_fee: Option<Balance>,
+        size: u64,
+    }
+
+    /// Starts translating the files from the unversioned layout to v1, the
+    /// files are translated in batches on idle from the migration cursor
+    /// (see `migrate_file_layouts`), no file can be read or uploaded
+    /// meanwhile
+    pub struct MigrateFileLayout<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateFileLayout<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+            if on_chain_version >= 1 || <FileLayoutMigrationCursor<T>>::exists() {
+                return T::DbWeight::get().reads(2);
+            }
+
+            // the map prefix sorts right before the first file
+            let cursor = <Files<T> as frame_support::storage::StoragePrefixedMap<
+                FileInformation<T>,
+            >>::final_prefix();
+            <FileLayoutMigrationCursor<T>>::put(cursor.to_vec());
+
+            T::DbWeight::get().reads_writes(2, 1)
+        }
+    }
+
+    /// Translates the files after the migration cursor, bounded by weight &
+    /// per block limit. The servicer becomes the only replica, the file is
+    /// listed at its transfer fee, its royalties split evenly between the
+    /// uploader & big brother, it counts as charged at the translation and
+    /// has no chunk manifest until a servicer sets it. The file is indexed by
+    /// owner & uploader along the way, so once every file is translated the
+    /// v2 backfill is done as well and the storage version moves to 2
+    pub fn migrate_file_layouts<T: Config>(
+        now: BlockNumber<T>,
+        remaining_weight: Weight,
+    ) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let weight_per_file = db_weight.reads_writes(6, 7); // TODO: please benchmark
+        let mut consumed_weight = db_weight.reads_writes(1, 2);
+
+        if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_file)) {
+            return Weight::zero();
+        }
+
+        let Some(cursor) = <FileLayoutMigrationCursor<T>>::get() else {
+            return db_weight.reads(1);
+        };
+
+        let max_files = remaining_weight
+            .saturating_sub(consumed_weight)
+            .ref_time()
+            .checked_div(weight_per_file.ref_time())
+            .unwrap_or(u64::MAX)
+            .min(T::MaxFileIndexBackfillsPerBlock::get() as u64);
+        let files = old::Files::<T>::iter_from(cursor)
+            .take(max_files as usize)
+            .collect::<sp_std::vec::Vec<_>>();
+
+        match files.last() {
+            | Some((file, _)) if files.len() as u64 == max_files => {
+                <FileLayoutMigrationCursor<T>>::put(old::Files::<T>::hashed_key_for(file));
+            },
+            | _ => {
+                // every file is translated & indexed
+                <FileLayoutMigrationCursor<T>>::kill();
+                StorageVersion::new(2).put::<Pallet<T>>();
+            },
+        }
+
+        for (file, old) in files {
+            consumed_weight = consumed_weight.saturating_add(weight_per_file);
+
+            let replica = FileReplica {
+                servicer: old.servicer,
+                attester: old.attester,
+                attester_revoked: ngr_svrg::Pallet::<T>::is_attester_revoked(&old.attester),
+            };
+            let royalties = Pallet::<T>::default_royalties(&old.uploader, &old.big_brother);
+            let file_info = FileInformation::<T> {
+                hash: old.hash,
+                uploader: old.uploader,
+                big_brother: old.big_brother,
+                owner: old.owner,
+                transfer_fee: old.transfer_fee,
+                download_fee: old.download_fee,
+                size: old.size,
+                chunk_size: 0,
+                chunk_root: FileHash::default(),
+                pricing_asset: None,
+                replication_factor: 1,
+                replicas: frame_support::BoundedVec::truncate_from(sp_std::vec![replica]),
+                royalties: frame_support::BoundedVec::truncate_from(royalties),
+                last_charged_at: now,
+                status: FileStatus::Active,
+            };
+            <FilesByAttester<T>>::insert(old.attester, &file, ());
+            <Listings<T>>::insert(
+                &file,
+                Listing::FixedPrice {
+                    price: old.transfer_fee,
+                },
+            );
+            Pallet::<T>::index_existing_file(&file, &file_info);
+            <Files<T>>::insert(&file, file_info);
+        }
+
+        consumed_weight
+    }
+}
+
+pub mod v2 {
+    use crate::*;
+    use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use frame_support::weights::Weight;
+
+    /// Starts backfilling the owner & uploader file indexes from the existing
+    /// files, the files are indexed in batches on idle from the backfill
+    /// cursor, the per account bound is not enforced on files that already
+    /// exist
+    pub struct BackfillFileIndexes<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for BackfillFileIndexes<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+            if on_chain_version != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            // the map prefix sorts right before the first file
+            let cursor = <Files<T> as frame_support::storage::StoragePrefixedMap<
+                FileInformation<T>,
+            >>::final_prefix();
+            <FileIndexBackfillCursor<T>>::put(cursor.to_vec());
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(1, 2)
+        }
+    }
+}
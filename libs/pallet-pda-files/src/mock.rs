@@ -0,0 +1,291 @@
+//! Mock runtime for the PDA Files pallet tests
+
+use crate as nagara_pda_files;
+use frame_support::traits::{ConstU128, ConstU32, ConstU64};
+use sp_runtime::{AccountId32, BuildStorage};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const BIG_BROTHER: AccountId = AccountId32::new([1u8; 32]);
+pub const UPLOADER: AccountId = AccountId32::new([2u8; 32]);
+pub const SERVICER: AccountId = AccountId32::new([3u8; 32]);
+pub const OTHER_SERVICER: AccountId = AccountId32::new([4u8; 32]);
+pub const BUYER: AccountId = AccountId32::new([5u8; 32]);
+pub const OTHER_BUYER: AccountId = AccountId32::new([6u8; 32]);
+
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+pub const STORAGE_PERIOD: BlockNumber = 10;
+pub const CHALLENGE_RESPONSE_PERIOD: BlockNumber = 5;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Identity: pallet_identity,
+        Assets: pallet_assets,
+        BigBrotherCouncil: nagara_council_bigbrothers,
+        ServicerRegistry: nagara_registry_servicers,
+        PDAFiles: nagara_pda_files,
+    }
+);
+
+frame_support::parameter_types! {
+    pub BurnAddress: AccountId = AccountId32::new([255u8; 32]);
+    pub const BigBrotherDownloadFeeDistribution: sp_runtime::Percent =
+        sp_runtime::Percent::from_percent(10);
+    pub const EarlyTerminationFee: sp_runtime::Percent = sp_runtime::Percent::from_percent(10);
+    pub const RegistrationFeeBigBrotherDistribution: sp_runtime::Percent =
+        sp_runtime::Percent::from_percent(50);
+    pub const RoyaltyFeeDistribution: sp_runtime::Percent = sp_runtime::Percent::from_percent(10);
+    pub const ServicerUploadFeeDistribution: sp_runtime::Percent =
+        sp_runtime::Percent::from_percent(50);
+}
+
+impl frame_system::Config for Test {
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type AccountId = AccountId;
+    type BaseCallFilter = frame_support::traits::Everything;
+    type Block = Block;
+    type BlockHashCount = ConstU64<250>;
+    type BlockLength = ();
+    type BlockWeights = ();
+    type DbWeight = ();
+    type Hash = sp_core::H256;
+    type Hashing = sp_runtime::traits::BlakeTwo256;
+    type Lookup = sp_runtime::traits::IdentityLookup<AccountId>;
+    type MaxConsumers = ConstU32<16>;
+    type Nonce = u64;
+    type OnKilledAccount = ();
+    type OnNewAccount = ();
+    type OnSetCode = ();
+    type PalletInfo = PalletInfo;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type SS58Prefix = ();
+    type SystemWeightInfo = ();
+    type Version = ();
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type Extrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+    type OverarchingCall = RuntimeCall;
+}
+
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type MaxHolds = ConstU32<4>;
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type WeightInfo = ();
+}
+
+impl pallet_identity::Config for Test {
+    type BasicDeposit = ConstU128<10>;
+    type Currency = Balances;
+    type FieldDeposit = ConstU128<1>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxAdditionalFields = ConstU32<2>;
+    type MaxRegistrars = ConstU32<4>;
+    type MaxSubAccounts = ConstU32<2>;
+    type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type Slashed = ();
+    type SubAccountDeposit = ConstU128<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+    type ApprovalDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Balance = Balance;
+    type CallbackHandle = ();
+    type CreateOrigin =
+        frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    type Currency = Balances;
+    type Extra = ();
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type Freezer = ();
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type RemoveItemsLimit = ConstU32<1024>;
+    type RuntimeEvent = RuntimeEvent;
+    type StringLimit = ConstU32<50>;
+    type WeightInfo = ();
+}
+
+impl nagara_council_bigbrothers::Config for Test {
+    type BurnAddress = BurnAddress;
+    type Currency = Balances;
+    type InitialMinimumTransactionFee = ConstU128<1>;
+    type InitialWeightToFeeDivider = ConstU64<1>;
+    type InitialWeightToFeeMultiplier = ConstU64<1>;
+    type MaxMembers = ConstU32<4>;
+    type RegistrationDepositAmount = ConstU128<100>;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl nagara_registry_servicers::Config for Test {
+    type AdvertisementTtl = ConstU64<100>;
+    type BindingDepositAmount = ConstU128<10>;
+    type Currency = Balances;
+    type HeartbeatPriority = ConstU64<1>;
+    type HeartbeatWindow = ConstU64<1_000>;
+    type MaxAdvertisedEndpoints = ConstU32<4>;
+    type MaxAdvertisedProtocols = ConstU32<4>;
+    type MaxAdvertisementEntryLength = ConstU32<64>;
+    type MaxAttesterSupplyBatch = ConstU32<4>;
+    type MaxCooperativeMembers = ConstU32<4>;
+    type MaxHeartbeatChecksPerBlock = ConstU32<8>;
+    type MaxMediatorQuota = ConstU32<4>;
+    type MaxMediatorScope = ConstU32<4>;
+    type MaxMediators = ConstU32<4>;
+    type MaxMissedHeartbeats = ConstU32<4>;
+    type MediatorWindow = ConstU64<10>;
+    type OnAttesterRevoked = PDAFiles;
+    type OnRegistrationFee = ();
+    type PeerRotationCooldown = ConstU64<10>;
+    type RegistrationFeeAmount = ConstU128<10>;
+    type RegistrationFeeBigBrotherDistribution = RegistrationFeeBigBrotherDistribution;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl nagara_pda_files::Config for Test {
+    type Assets = Assets;
+    type BigBrotherDownloadFeeDistribution = BigBrotherDownloadFeeDistribution;
+    type ChallengeResponsePeriod = ConstU64<CHALLENGE_RESPONSE_PERIOD>;
+    type Currency = Balances;
+    type EarlyTerminationFee = EarlyTerminationFee;
+    type GracePeriod = ConstU64<20>;
+    type LicenseDuration = ConstU64<100>;
+    type MaxAccessListEntries = ConstU32<4>;
+    type MaxAuctionDuration = ConstU64<100>;
+    type MaxChunkSize = ConstU32<64>;
+    type MaxFileEntryRemovalsPerBlock = ConstU32<2>;
+    type MaxFileIndexBackfillsPerBlock = ConstU32<2>;
+    type MaxFilesPerAccount = ConstU32<2>;
+    type MaxOffersPerFile = ConstU32<2>;
+    type MaxProofDepth = ConstU32<8>;
+    type MaxReplicaRevocationsPerBlock = ConstU32<8>;
+    type MaxReplicas = ConstU32<4>;
+    type MaxRoyaltyBeneficiaries = ConstU32<4>;
+    type MaxStorageFeeChargesPerBlock = ConstU32<8>;
+    type MinDownloadFeePerByte = FeePerByte<1>;
+    type OnAssetFeeRemainder = ();
+    type OnFeeRemainder = ();
+    type PricingNoticePeriod = ConstU64<10>;
+    type Randomness = ZeroRandomness;
+    type RoyaltyFeeDistribution = RoyaltyFeeDistribution;
+    type RuntimeEvent = RuntimeEvent;
+    type ServicerUploadFeeDistribution = ServicerUploadFeeDistribution;
+    type StorageFeePerBytePerPeriod = FeePerByte<2>;
+    type StoragePeriod = ConstU64<STORAGE_PERIOD>;
+    type UploadFeePerByte = FeePerByte<1>;
+}
+
+/// Flat fee per byte
+pub struct FeePerByte<const FEE: u128>;
+
+impl<const FEE: u128> nagara_pda_files::FeeFromBytes for FeePerByte<FEE> {
+    type Balance = Balance;
+
+    fn bytes_to_fee(size: u64) -> Self::Balance {
+        Balance::from(size).saturating_mul(FEE)
+    }
+}
+
+/// Randomness always challenging the first chunk of the first replica
+pub struct ZeroRandomness;
+
+impl frame_support::traits::Randomness<sp_core::H256, BlockNumber> for ZeroRandomness {
+    fn random(_: &[u8]) -> (sp_core::H256, BlockNumber) {
+        (sp_core::H256::zero(), 0)
+    }
+}
+
+pub fn attester(seed: u8) -> nagara_pda_files::AttesterId {
+    sp_core::ed25519::Public::from_raw([seed; 32])
+}
+
+fn peer(seed: u8) -> nagara_registry_servicers::PeerId {
+    sp_core::ed25519::Public::from_raw([seed.saturating_add(100); 32])
+}
+
+/// big brother in the council, two servicers each binded to an attester
+/// supplied by the big brother
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: [
+            BIG_BROTHER,
+            UPLOADER,
+            SERVICER,
+            OTHER_SERVICER,
+            BUYER,
+            OTHER_BUYER,
+        ]
+        .into_iter()
+        .map(|account| (account, INITIAL_BALANCE))
+        .collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    nagara_council_bigbrothers::GenesisConfig::<Test> {
+        elder: None,
+        big_brothers: vec![BIG_BROTHER],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    nagara_registry_servicers::GenesisConfig::<Test> {
+        attesters: [1u8, 2]
+            .into_iter()
+            .map(|seed| {
+                (
+                    BIG_BROTHER,
+                    nagara_registry_servicers::RemoteAttestationDeviceSupplyArgs {
+                        id: attester(seed),
+                        guid: [seed; 16],
+                        serial_number: seed as u32,
+                    },
+                )
+            })
+            .collect(),
+        mediators: vec![],
+        servicers: vec![
+            (SERVICER, vec![(attester(1), peer(1))]),
+            (OTHER_SERVICER, vec![(attester(2), peer(2))]),
+        ],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+
+    ext
+}
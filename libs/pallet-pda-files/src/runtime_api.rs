@@ -0,0 +1,35 @@
+//! Runtime API definition for the PDA Files pallet
+
+sp_api::decl_runtime_apis! {
+    pub trait PdaFilesApi<AccountId, AssetId, Balance, BlockNumber>
+    where
+        AccountId: codec::Codec,
+        AssetId: codec::Codec,
+        Balance: codec::Codec,
+        BlockNumber: codec::Codec,
+    {
+        /// How `who` may download `file` right now: free, by paying the
+        /// download fee, or not at all
+        fn download_access(file: AccountId, who: AccountId) -> Option<crate::DownloadAccess<Balance>>;
+        /// Download license of `who` on `file`, expired ones included
+        fn license(file: AccountId, who: AccountId) -> Option<crate::License<BlockNumber>>;
+        /// File by its account
+        fn file(
+            file: AccountId,
+        ) -> Option<crate::FileSummary<AccountId, AssetId, Balance, BlockNumber>>;
+        /// File account derived from a content hash & uploader
+        fn file_account_of(hash: crate::FileHash, uploader: AccountId) -> AccountId;
+        /// File (account & summary) by its content hash
+        fn file_by_hash(
+            hash: crate::FileHash,
+        ) -> Option<(AccountId, crate::FileSummary<AccountId, AssetId, Balance, BlockNumber>)>;
+        /// Files owned by `owner`
+        fn files_of_owner(owner: AccountId) -> sp_std::vec::Vec<AccountId>;
+        /// Upload, minimum download, storage (per period) & minimum transfer
+        /// fees of a file size, `None` if the pricing asset is not accepted
+        fn quote(size: u64, pricing_asset: Option<AssetId>) -> Option<crate::FeeQuote<Balance>>;
+        /// Current download, transfer (fixed price listing), minimum bid (open
+        /// auction) & storage fees of a file
+        fn file_quote(file: AccountId) -> Option<crate::FileQuote<Balance>>;
+    }
+}
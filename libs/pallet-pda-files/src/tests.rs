@@ -0,0 +1,623 @@
+//! Unit tests for the PDA Files pallet
+
+use crate::{mock::PDAFiles, *};
+use sp_runtime::AccountId32;
+
+fn file_account(hash: &FileHash, uploader: &AccountId32) -> AccountId32 {
+    derive_file_account(hash, uploader)
+}
+
+#[test]
+fn file_account_is_deterministic() {
+    let uploader = AccountId32::new([1u8; 32]);
+
+    assert_eq!(
+        file_account(&[7u8; 32], &uploader),
+        file_account(&[7u8; 32], &uploader)
+    );
+}
+
+#[test]
+fn file_accounts_differ_by_uploader() {
+    let hash = [7u8; 32];
+    let alice = AccountId32::new([1u8; 32]);
+    let bob = AccountId32::new([2u8; 32]);
+
+    assert_ne!(file_account(&hash, &alice), file_account(&hash, &bob));
+}
+
+#[test]
+fn file_accounts_differ_by_hash() {
+    let uploader = AccountId32::new([1u8; 32]);
+    let hash = [7u8; 32];
+    let mut other_hash = hash;
+    other_hash[31] = 8; // past the bytes a raw seed keeps
+
+    assert_ne!(
+        file_account(&hash, &uploader),
+        file_account(&other_hash, &uploader)
+    );
+}
+
+#[test]
+fn file_account_is_not_the_pallet_account() {
+    let pallet_account: AccountId32 =
+        sp_runtime::traits::AccountIdConversion::into_account_truncating(&PALLET_IDENTIFICATION);
+
+    assert_ne!(
+        file_account(&[0u8; 32], &AccountId32::new([0u8; 32])),
+        pallet_account
+    );
+}
+
+const CHUNK_SIZE: u32 = 16;
+
+fn content() -> Vec<u8> {
+    (0u8..64).collect()
+}
+
+fn upload_args(content: &[u8]) -> FileInformationArgs<mock::AccountId, mock::Balance> {
+    FileInformationArgs {
+        hash: sp_core::hashing::blake2_256(content),
+        uploader: mock::UPLOADER,
+        big_brother: mock::BIG_BROTHER,
+        replication_factor: 2,
+        replicas: vec![
+            (mock::SERVICER, mock::attester(1)),
+            (mock::OTHER_SERVICER, mock::attester(2)),
+        ],
+        royalties: vec![],
+        transfer_fee: 1_000,
+        download_fee: Some(100),
+        size: content.len() as u64,
+        chunk_size: CHUNK_SIZE,
+        chunk_root: ngr_chmk::root_of(content, CHUNK_SIZE),
+    }
+}
+
+/// upload the test content replicated on both servicers, with the file
+/// account funded for its storage fees
+fn upload_file() -> mock::AccountId {
+    let args = upload_args(&content());
+    let file = PDAFiles::file_account_of(&args.hash, &args.uploader);
+    frame_support::assert_ok!(PDAFiles::servicer_upload_derived(
+        mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+        args,
+        None,
+    ));
+    let _ = <mock::Balances as frame_support::traits::Currency<_>>::deposit_creating(
+        &file,
+        mock::INITIAL_BALANCE,
+    );
+
+    file
+}
+
+fn free_balance(who: &mock::AccountId) -> mock::Balance {
+    <mock::Balances as frame_support::traits::Currency<_>>::free_balance(who)
+}
+
+fn reputation(servicer: &mock::AccountId) -> (u32, u32) {
+    let servicer = ngr_svrg::Pallet::<mock::Test>::servicers(servicer).unwrap();
+
+    (servicer.rep_positive, servicer.rep_negative)
+}
+
+/// storage period is over, challenge the first chunk of the first replica
+fn issue_challenge(file: &mock::AccountId) -> mock::BlockNumber {
+    let now = 1 + mock::STORAGE_PERIOD;
+    mock::System::set_block_number(now);
+    frame_support::assert_ok!(PDAFiles::servicer_take_storage_fee(
+        mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+        file.clone(),
+    ));
+
+    now
+}
+
+fn chunk_and_proof(
+    index: u32,
+) -> (
+    frame_support::BoundedVec<u8, <mock::Test as Config>::MaxChunkSize>,
+    frame_support::BoundedVec<FileHash, <mock::Test as Config>::MaxProofDepth>,
+) {
+    let content = content();
+    let leaves = ngr_chmk::leaves_of(&content, CHUNK_SIZE);
+    let start = (index * CHUNK_SIZE) as usize;
+    let chunk = content[start..start + CHUNK_SIZE as usize].to_vec();
+    let proof = ngr_chmk::proof(&leaves, index).unwrap();
+
+    (chunk.try_into().unwrap(), proof.try_into().unwrap())
+}
+
+#[test]
+fn due_file_is_challenged() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        let now = issue_challenge(&file);
+
+        let challenge = PDAFiles::challenges(&file).unwrap();
+        assert_eq!(challenge.servicer, mock::SERVICER);
+        assert_eq!(challenge.attester, mock::attester(1));
+        assert_eq!(challenge.chunk_index, 0);
+        assert_eq!(challenge.deadline, now + mock::CHALLENGE_RESPONSE_PERIOD);
+        frame_support::assert_noop!(
+            PDAFiles::servicer_take_storage_fee(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                file.clone(),
+            ),
+            Error::<mock::Test>::ChallengeAlreadyIssued,
+        );
+    });
+}
+
+#[test]
+fn answered_challenge_pays_only_the_answering_replica() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        issue_challenge(&file);
+        let file_balance = free_balance(&file);
+        let servicer_balance = free_balance(&mock::SERVICER);
+        let other_servicer_balance = free_balance(&mock::OTHER_SERVICER);
+        let (rep_positive, _) = reputation(&mock::SERVICER);
+        let other_reputation = reputation(&mock::OTHER_SERVICER);
+        let (chunk, proof) = chunk_and_proof(0);
+
+        frame_support::assert_ok!(PDAFiles::servicer_answer_challenge(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            file.clone(),
+            chunk,
+            proof,
+        ));
+
+        let storage_fee = 2 * content().len() as mock::Balance;
+        assert!(PDAFiles::challenges(&file).is_none());
+        assert_eq!(free_balance(&file), file_balance - storage_fee);
+        assert_eq!(
+            free_balance(&mock::SERVICER),
+            servicer_balance + storage_fee / 2
+        );
+        assert_eq!(free_balance(&mock::OTHER_SERVICER), other_servicer_balance);
+        assert_eq!(reputation(&mock::SERVICER).0, rep_positive + 1);
+        assert_eq!(reputation(&mock::OTHER_SERVICER), other_reputation);
+    });
+}
+
+#[test]
+fn challenge_with_wrong_proof_is_rejected() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        issue_challenge(&file);
+        let (_, proof) = chunk_and_proof(0);
+        let (other_chunk, other_proof) = chunk_and_proof(1);
+
+        frame_support::assert_noop!(
+            PDAFiles::servicer_answer_challenge(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                file.clone(),
+                other_chunk.clone(),
+                proof,
+            ),
+            Error::<mock::Test>::InvalidStorageProof,
+        );
+        frame_support::assert_noop!(
+            PDAFiles::servicer_answer_challenge(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                file.clone(),
+                other_chunk.clone(),
+                other_proof.clone(),
+            ),
+            Error::<mock::Test>::InvalidStorageProof,
+        );
+        frame_support::assert_noop!(
+            PDAFiles::servicer_answer_challenge(
+                mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+                file.clone(),
+                other_chunk,
+                other_proof,
+            ),
+            Error::<mock::Test>::NotFileServicer,
+        );
+    });
+}
+
+#[test]
+fn timed_out_challenge_is_missed() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        let now = issue_challenge(&file);
+        let file_balance = free_balance(&file);
+        let (_, rep_negative) = reputation(&mock::SERVICER);
+        let deadline = now + mock::CHALLENGE_RESPONSE_PERIOD;
+
+        mock::System::set_block_number(deadline + 1);
+        let (chunk, proof) = chunk_and_proof(0);
+        frame_support::assert_noop!(
+            PDAFiles::servicer_answer_challenge(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                file.clone(),
+                chunk,
+                proof,
+            ),
+            Error::<mock::Test>::ChallengeExpired,
+        );
+
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            deadline + 1,
+            frame_support::weights::Weight::MAX,
+        );
+
+        assert!(PDAFiles::challenges(&file).is_none());
+        assert_eq!(PDAFiles::missed_challenges(&mock::SERVICER), 1);
+        assert_eq!(PDAFiles::missed_challenges(&mock::OTHER_SERVICER), 0);
+        assert_eq!(reputation(&mock::SERVICER).1, rep_negative + 1);
+        assert_eq!(free_balance(&file), file_balance);
+        assert_eq!(
+            PDAFiles::files(&file).unwrap().last_charged_at,
+            deadline + 1
+        );
+    });
+}
+
+#[test]
+fn file_without_chunk_manifest_withholds_the_fee() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        <Files<mock::Test>>::mutate(&file, |file_info| {
+            file_info.as_mut().unwrap().chunk_size = 0; // as migrated
+        });
+        let file_balance = free_balance(&file);
+        let servicer_balance = free_balance(&mock::SERVICER);
+        let now = issue_challenge(&file);
+
+        assert!(PDAFiles::challenges(&file).is_none());
+        assert_eq!(free_balance(&file), file_balance);
+        assert_eq!(free_balance(&mock::SERVICER), servicer_balance);
+        assert_eq!(PDAFiles::files(&file).unwrap().last_charged_at, now);
+        mock::System::assert_last_event(
+            Event::<mock::Test>::StorageFeeWithheld {
+                file,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn revoked_replicas_are_flagged_on_idle() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+
+        frame_support::assert_ok!(mock::ServicerRegistry::bb_attester_revoke(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::attester(1),
+        ));
+        assert!(PDAFiles::pending_replica_revocations(mock::attester(1)).is_some());
+        assert!(!PDAFiles::files(&file).unwrap().replicas[0].attester_revoked);
+
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            1,
+            frame_support::weights::Weight::MAX,
+        );
+
+        let replicas = PDAFiles::files(&file).unwrap().replicas;
+        assert!(replicas[0].attester_revoked);
+        assert!(!replicas[1].attester_revoked);
+        assert!(PDAFiles::pending_replica_revocations(mock::attester(1)).is_none());
+        assert!(PDAFiles::replica_revocation_cursor().is_none());
+        mock::System::assert_has_event(
+            Event::<mock::Test>::FileAttesterRevoked {
+                file,
+                attester: mock::attester(1),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn revoked_replicas_are_not_challenged() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        frame_support::assert_ok!(mock::ServicerRegistry::bb_attester_revoke(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::attester(1),
+        ));
+        issue_challenge(&file);
+
+        let challenge = PDAFiles::challenges(&file).unwrap();
+        assert_eq!(challenge.servicer, mock::OTHER_SERVICER);
+        assert_eq!(challenge.attester, mock::attester(2));
+    });
+}
+
+#[test]
+fn files_are_migrated_in_batches() {
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    mock::new_test_ext().execute_with(|| {
+        frame_support::traits::StorageVersion::new(0).put::<PDAFiles>();
+        let files = (10u8..13)
+            .map(|seed| AccountId32::new([seed; 32]))
+            .collect::<Vec<_>>();
+        for (index, file) in files.iter().enumerate() {
+            // unversioned layout
+            let old_file = (
+                [index as u8; 32],
+                mock::UPLOADER,
+                mock::BIG_BROTHER,
+                mock::SERVICER,
+                mock::attester(1),
+                mock::UPLOADER,
+                1_000 as mock::Balance,
+                Some(100 as mock::Balance),
+                64u64,
+            );
+            frame_support::storage::unhashed::put(
+                &<Files<mock::Test>>::hashed_key_for(file),
+                &old_file,
+            );
+        }
+
+        migrations::v1::MigrateFileLayout::<mock::Test>::on_runtime_upgrade();
+        frame_support::assert_noop!(
+            PDAFiles::servicer_upload_derived(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                upload_args(&content()),
+                None,
+            ),
+            Error::<mock::Test>::FileMigrationInProgress,
+        );
+
+        // two files per block
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            1,
+            frame_support::weights::Weight::MAX,
+        );
+        assert!(PDAFiles::file_layout_migration_cursor().is_some());
+        assert_eq!(PDAFiles::owned_file_counts(&mock::UPLOADER), 2);
+
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            1,
+            frame_support::weights::Weight::MAX,
+        );
+        assert!(PDAFiles::file_layout_migration_cursor().is_none());
+        assert_eq!(PDAFiles::owned_file_counts(&mock::UPLOADER), 3);
+        assert_eq!(
+            <PDAFiles as frame_support::traits::GetStorageVersion>::on_chain_storage_version(),
+            2
+        );
+
+        let half = sp_runtime::Permill::from_percent(50);
+        for file in &files {
+            let file_info = PDAFiles::files(file).unwrap();
+            assert_eq!(
+                file_info.royalties.into_inner(),
+                vec![(mock::UPLOADER, half), (mock::BIG_BROTHER, half)]
+            );
+            assert!(!file_info.has_chunk_manifest());
+            assert_eq!(
+                PDAFiles::listings(file),
+                Some(Listing::FixedPrice {
+                    price: 1_000
+                })
+            );
+        }
+    });
+}
+
+#[test]
+fn download_access_is_free_purchase_or_denied() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+
+        assert_eq!(
+            PDAFiles::download_access(&file, &mock::BUYER),
+            Some(DownloadAccess::Purchase {
+                fee: 100
+            })
+        );
+        frame_support::assert_ok!(PDAFiles::servicer_download(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            file.clone(),
+            mock::BUYER,
+        ));
+        assert_eq!(
+            PDAFiles::download_access(&file, &mock::BUYER),
+            Some(DownloadAccess::Free)
+        );
+
+        frame_support::assert_ok!(PDAFiles::owner_set_access_mode(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+            AccessMode::Allowlist,
+        ));
+        assert_eq!(
+            PDAFiles::download_access(&file, &mock::BUYER),
+            Some(DownloadAccess::Denied)
+        );
+        assert_eq!(PDAFiles::download_access(&mock::BUYER, &mock::BUYER), None);
+    });
+}
+
+#[test]
+fn deleted_file_entries_are_removed_on_idle() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        frame_support::assert_ok!(PDAFiles::owner_access_list_set(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+            mock::OTHER_BUYER,
+            None,
+        ));
+        for downloader in [mock::BUYER, mock::OTHER_BUYER] {
+            frame_support::assert_ok!(PDAFiles::servicer_download(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                file.clone(),
+                downloader,
+            ));
+        }
+
+        frame_support::assert_ok!(PDAFiles::owner_delete_file(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+        ));
+        assert!(PDAFiles::deleted_files(&file).is_some());
+        frame_support::assert_noop!(
+            PDAFiles::servicer_upload_derived(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                upload_args(&content()),
+                None,
+            ),
+            Error::<mock::Test>::FileEntriesPendingRemoval,
+        );
+
+        // two entries per block, the access list entry first
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            1,
+            frame_support::weights::Weight::MAX,
+        );
+        assert!(PDAFiles::access_lists(&file, &mock::OTHER_BUYER).is_none());
+        assert_eq!(<Licenses<mock::Test>>::iter_prefix(&file).count(), 1);
+        assert!(PDAFiles::deleted_files(&file).is_some());
+
+        <PDAFiles as frame_support::traits::OnIdle<_>>::on_idle(
+            1,
+            frame_support::weights::Weight::MAX,
+        );
+        assert_eq!(<Licenses<mock::Test>>::iter_prefix(&file).count(), 0);
+        assert!(PDAFiles::deleted_files(&file).is_none());
+        assert!(PDAFiles::deleted_file_cursor().is_none());
+    });
+}
+
+#[test]
+fn listing_price_changes_wait_for_the_notice_period() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        frame_support::assert_ok!(PDAFiles::owner_list_file(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+            2_000,
+        ));
+
+        assert_eq!(
+            PDAFiles::listings(&file),
+            Some(Listing::FixedPrice {
+                price: 1_000
+            })
+        );
+        let update = PDAFiles::pending_pricing(&file).unwrap();
+        assert_eq!(update.transfer_fee, 2_000);
+        assert_eq!(update.download_fee, Some(100));
+
+        mock::System::set_block_number(update.effective_at);
+        frame_support::assert_noop!(
+            PDAFiles::any_buy_file(
+                mock::RuntimeOrigin::signed(mock::BUYER),
+                file.clone(),
+                1_000,
+            ),
+            Error::<mock::Test>::PriceAboveLimit,
+        );
+        frame_support::assert_ok!(PDAFiles::any_buy_file(
+            mock::RuntimeOrigin::signed(mock::BUYER),
+            file.clone(),
+            2_000,
+        ));
+        assert_eq!(PDAFiles::files(&file).unwrap().owner, mock::BUYER);
+    });
+}
+
+#[test]
+fn failed_auction_settlement_refunds_the_bid() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        frame_support::assert_ok!(PDAFiles::owner_start_auction(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+            500,
+            10,
+        ));
+        frame_support::assert_ok!(PDAFiles::any_bid(
+            mock::RuntimeOrigin::signed(mock::BUYER),
+            file.clone(),
+            600,
+        ));
+
+        // the winner already owns as many files as allowed
+        <OwnedFileCounts<mock::Test>>::insert(
+            mock::BUYER,
+            <<mock::Test as Config>::MaxFilesPerAccount as sp_core::Get<u32>>::get(),
+        );
+        mock::System::set_block_number(11);
+        frame_support::assert_ok!(PDAFiles::any_settle_auction(
+            mock::RuntimeOrigin::signed(mock::OTHER_BUYER),
+            file.clone(),
+        ));
+
+        mock::System::assert_last_event(
+            Event::<mock::Test>::AuctionSettlementFailed {
+                file: file.clone(),
+                bidder: mock::BUYER,
+                amount: 600,
+                error: Error::<mock::Test>::TooManyFilesPerAccount.into(),
+            }
+            .into(),
+        );
+        assert_eq!(PDAFiles::files(&file).unwrap().owner, mock::UPLOADER);
+        assert!(PDAFiles::listings(&file).is_none());
+        assert!(PDAFiles::highest_bids(&file).is_none());
+        assert_eq!(free_balance(&mock::BUYER), mock::INITIAL_BALANCE);
+        assert_eq!(
+            <mock::Balances as frame_support::traits::ReservableCurrency<_>>::reserved_balance(
+                &mock::BUYER
+            ),
+            0,
+        );
+    });
+}
+
+#[test]
+fn file_quote_follows_the_listing() {
+    mock::new_test_ext().execute_with(|| {
+        let file = upload_file();
+        let quote = PDAFiles::file_quote(&file).unwrap();
+        assert_eq!(quote.transfer, Some(1_000));
+        assert_eq!(quote.min_bid, None);
+
+        frame_support::assert_ok!(PDAFiles::owner_start_auction(
+            mock::RuntimeOrigin::signed(mock::UPLOADER),
+            file.clone(),
+            500,
+            10,
+        ));
+        let quote = PDAFiles::file_quote(&file).unwrap();
+        assert_eq!(quote.transfer, None);
+        assert_eq!(quote.min_bid, Some(500));
+
+        frame_support::assert_ok!(PDAFiles::any_bid(
+            mock::RuntimeOrigin::signed(mock::BUYER),
+            file.clone(),
+            600,
+        ));
+        assert_eq!(PDAFiles::file_quote(&file).unwrap().min_bid, Some(601));
+
+        mock::System::set_block_number(11);
+        assert_eq!(PDAFiles::file_quote(&file).unwrap().min_bid, None);
+    });
+}
+
+#[test]
+fn genesis_files_are_uploaded_to_their_derived_account() {
+    mock::new_test_ext().execute_with(|| {
+        let args = upload_args(&content());
+        let file = file_account(&args.hash, &args.uploader);
+        frame_support::traits::BuildGenesisConfig::build(&GenesisConfig::<mock::Test> {
+            files: vec![args.clone()],
+        });
+
+        assert_eq!(PDAFiles::hashes(args.hash), Some(file.clone()));
+        assert_eq!(PDAFiles::files(&file).unwrap().owner, mock::UPLOADER);
+    });
+}
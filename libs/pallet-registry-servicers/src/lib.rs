@@ -3,16 +3,51 @@
 pub use nagara_council_bigbrothers as ngr_bbcm;
 pub use pallet::*;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 pub type AccountTypeOf<T> = <T as frame_system::Config>::AccountId;
 pub type AttesterId = sp_core::ed25519::Public;
+pub type AttesterSignature = sp_core::ed25519::Signature;
 pub type PeerId = sp_core::ed25519::Public;
+pub type RegionCode = [u8; 2]; // ISO 3166-1 alpha-2
 pub type BalanceCurrencyTypeOf<T> =
     <<T as Config>::Currency as frame_support::traits::Currency<AccountTypeOf<T>>>::Balance;
+pub type NegativeImbalanceTypeOf<T> = <<T as Config>::Currency as frame_support::traits::Currency<
+    AccountTypeOf<T>,
+>>::NegativeImbalance;
 pub type BalanceInspectTypeOf<T> = <<T as Config>::Currency as frame_support::traits::fungible::Inspect<AccountTypeOf<T>>>::Balance;
 pub type Guid = [u8; 16];
 pub type UniqueMap<K, V> = sp_std::collections::btree_map::BTreeMap<K, V>;
+pub type UniqueSet<K> = sp_std::collections::btree_set::BTreeSet<K>;
 
+pub const ATTESTER_KEY_TYPE: sp_core::crypto::KeyTypeId = sp_core::crypto::KeyTypeId(*b"ngra");
 pub const PALLET_IDENTIFICATION: frame_support::PalletId = frame_support::PalletId(*b"ngr/svrg");
+pub const SIGNING_CONTEXT_ADVERTISEMENT: [u8; 4] = *b"advt";
+pub const SIGNING_CONTEXT_HEARTBEAT: [u8; 4] = *b"beat";
+pub const SIGNING_CONTEXT_PEER_ROTATION: [u8; 4] = *b"peer";
+
+/// Message to be signed by an attester key for the given context
+pub fn attester_signing_message<P: codec::Encode>(
+    context: [u8; 4],
+    payload: &P,
+) -> sp_std::vec::Vec<u8> {
+    codec::Encode::encode(&(PALLET_IDENTIFICATION, context, payload))
+}
+
+/// Hook for other pallets keeping state attested by an attester
+pub trait OnAttesterRevoked {
+    /// Called once an attester is revoked
+    fn on_attester_revoked(attester_id: &AttesterId);
+}
+
+impl OnAttesterRevoked for () {
+    fn on_attester_revoked(_: &AttesterId) {}
+}
 
 /// This pallet is used for registrations of the servicers, including their
 /// reputations
@@ -24,12 +59,18 @@ pub mod pallet {
 
     // region: Pallet Declaration
 
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + ngr_bbcm::Config {
+    pub trait Config:
+        frame_system::Config
+        + frame_system::offchain::SendTransactionTypes<Call<Self>>
+        + ngr_bbcm::Config {
         /// Runtime Event registrar
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Overarching hold reason.
@@ -52,11 +93,62 @@ pub mod pallet {
         /// only once per existence
         #[pallet::constant]
         type RegistrationFeeAmount: sp_core::Get<BalanceCurrencyTypeOf<Self>>;
+        /// Supplying big brother portion of the registration fee
+        #[pallet::constant]
+        type RegistrationFeeBigBrotherDistribution: sp_core::Get<sp_runtime::Percent>;
+        /// Handler of the registration fee left after the big brother portion
+        type OnRegistrationFee: frame_support::traits::OnUnbalanced<NegativeImbalanceTypeOf<Self>>;
         /// Maximum mediator for servicers, mediator is a role that can be
         /// filled by smart contracts to mediate services between the
         /// chain and dApps
         #[pallet::constant]
         type MaxMediators: sp_core::Get<u32>;
+        /// Maximum servicers in a scoped mediator's allowlist
+        #[pallet::constant]
+        type MaxMediatorScope: sp_core::Get<u32>;
+        /// Mediator quota window length, a mediator's quota is reset (and the
+        /// same attester can be voted on again) every window
+        #[pallet::constant]
+        type MediatorWindow: sp_core::Get<BlockNumberFor<Self>>;
+        /// Maximum mediator quota, also bounds the votes kept per mediator
+        #[pallet::constant]
+        type MaxMediatorQuota: sp_core::Get<u32>;
+        /// Minimum blocks between two peer id rotations of the same attester
+        #[pallet::constant]
+        type PeerRotationCooldown: sp_core::Get<BlockNumberFor<Self>>;
+        /// Maximum attesters supplied in a single batch
+        #[pallet::constant]
+        type MaxAttesterSupplyBatch: sp_core::Get<u32>;
+        /// Blocks until a service advertisement expires unless refreshed
+        #[pallet::constant]
+        type AdvertisementTtl: sp_core::Get<BlockNumberFor<Self>>;
+        /// Maximum endpoints (multiaddrs or URLs) per service advertisement
+        #[pallet::constant]
+        type MaxAdvertisedEndpoints: sp_core::Get<u32>;
+        /// Maximum protocols per service advertisement
+        #[pallet::constant]
+        type MaxAdvertisedProtocols: sp_core::Get<u32>;
+        /// Maximum length of a single endpoint or protocol entry
+        #[pallet::constant]
+        type MaxAdvertisementEntryLength: sp_core::Get<u32>;
+        /// Handler of attester revocations (e.g. flagging attested files)
+        type OnAttesterRevoked: OnAttesterRevoked;
+        /// Heartbeat window length, a binded attester should send one heartbeat
+        /// per window
+        #[pallet::constant]
+        type HeartbeatWindow: sp_core::Get<BlockNumberFor<Self>>;
+        /// Missed heartbeat windows before an attester is marked inactive
+        #[pallet::constant]
+        type MaxMissedHeartbeats: sp_core::Get<u32>;
+        /// Maximum servicers checked for missed heartbeats per block (on idle)
+        #[pallet::constant]
+        type MaxHeartbeatChecksPerBlock: sp_core::Get<u32>;
+        /// Heartbeat (unsigned) transaction priority
+        #[pallet::constant]
+        type HeartbeatPriority: sp_core::Get<TransactionPriority>;
+        /// Maximum member accounts of a servicer cooperative
+        #[pallet::constant]
+        type MaxCooperativeMembers: sp_core::Get<u32>;
     }
 
     // endregion
@@ -72,20 +164,136 @@ pub mod pallet {
         RemoteAttestationDevice<T::AccountId>,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn attester_devices)]
+    pub(super) type AttesterDevices<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, (Guid, u32), AttesterId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn attester_transfers)]
+    pub(super) type AttesterTransfers<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, AttesterId, T::AccountId>; // offered to
+
     #[pallet::storage]
     #[pallet::getter(fn mediators)]
     pub(super) type Mediators<T: Config> =
-        StorageValue<_, frame_support::BoundedBTreeSet<T::AccountId, T::MaxMediators>, ValueQuery>;
+        CountedStorageMap<_, frame_support::Blake2_128Concat, T::AccountId, MediatorInformation<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn mediator_usages)]
+    pub(super) type MediatorUsages<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, MediatorUsage, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn mediator_votes)]
+    pub(super) type MediatorVotes<T: Config> = StorageDoubleMap<
+        _,
+        frame_support::Blake2_128Concat,
+        T::AccountId,
+        frame_support::Blake2_128Concat,
+        AttesterId,
+        u32, // window of the last vote, only the last voting window is kept
+    >;
 
     #[pallet::storage]
     #[pallet::getter(fn servicers)]
     pub(super) type Servicers<T: Config> =
         StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, ServicerInformation>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn cooperatives)]
+    pub(super) type Cooperatives<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, T::AccountId, CooperativeInformation<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn peer_rotations)]
+    pub(super) type PeerRotations<T: Config> = StorageMap<
+        _,
+        frame_support::Blake2_128Concat,
+        AttesterId,
+        PeerRotationInformation<BlockNumberFor<T>>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn last_heartbeats)]
+    pub(super) type LastHeartbeats<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, AttesterId, u32>;
+
+    /// raw key of the last servicer checked for missed heartbeats
+    #[pallet::storage]
+    #[pallet::getter(fn heartbeat_sweep_cursor)]
+    pub(super) type HeartbeatSweepCursor<T: Config> = StorageValue<_, sp_std::vec::Vec<u8>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn advertisements)]
+    pub(super) type Advertisements<T: Config> =
+        StorageMap<_, frame_support::Blake2_128Concat, AttesterId, ServiceAdvertisement<T>>;
+
     // endregion
 
     // region: Genesis
 
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// (supplying big brother, attester)
+        pub attesters: sp_std::vec::Vec<(T::AccountId, RemoteAttestationDeviceSupplyArgs)>,
+        /// (mediator, quota per window), scoped to all servicers
+        pub mediators: sp_std::vec::Vec<(T::AccountId, u32)>,
+        /// (servicer, [(attester, peer id)]), registration fee & binding
+        /// deposit are taken from the servicer's genesis balance
+        pub servicers: sp_std::vec::Vec<(T::AccountId, sp_std::vec::Vec<(AttesterId, PeerId)>)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            // Attesters
+            for (big_brother, supply_args) in &self.attesters {
+                assert!(
+                    ngr_bbcm::Pallet::<T>::is_council_member(big_brother),
+                    "Initial Attester supplier is not a BigBrother!"
+                );
+                Pallet::<T>::try_supply_new_attester(big_brother.clone(), supply_args.clone())
+                    .expect("Initial Attester is invalid!");
+            }
+
+            // Mediators
+            assert!(
+                self.mediators.len() <= (T::MaxMediators::get() as usize),
+                "Initial Mediators exceeds Runtime Config!"
+            );
+
+            for (mediator, quota) in &self.mediators {
+                assert!(
+                    !<Mediators<T>>::contains_key(mediator),
+                    "Initial Mediator is duplicated!"
+                );
+                ngr_bbcm::Pallet::<T>::ensure_account_has_verified_legality(mediator)
+                    .expect("Initial Mediator has no verified legality!");
+                assert!(
+                    *quota <= T::MaxMediatorQuota::get(),
+                    "Initial Mediator quota exceeds Runtime Config!"
+                );
+                <Mediators<T>>::insert(
+                    mediator,
+                    MediatorInformation {
+                        scope: MediatorScope::All,
+                        quota: *quota,
+                    },
+                );
+            }
+
+            // Servicers
+            for (servicer, bindings) in &self.servicers {
+                for (attester_id, peer_id) in bindings {
+                    Pallet::<T>::try_bind_attester(servicer.clone(), *peer_id, *attester_id)
+                        .expect("Initial Servicer binding is invalid!");
+                }
+            }
+        }
+    }
+
     // endregion
 
     // region: Custom, Event, and Errors type
@@ -93,6 +301,7 @@ pub mod pallet {
     /// Remote Attestation Device Supply arguments
     #[derive(Clone, Eq, PartialEq)]
     #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(serde::Deserialize, serde::Serialize)]
     #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
     pub struct RemoteAttestationDeviceSupplyArgs {
         pub id: AttesterId,
@@ -109,6 +318,7 @@ pub mod pallet {
         pub serial_number: u32,
         pub guid: Guid,                // this is important for windows drivers
         pub binder: Option<AccountId>, // None means unbinded
+        pub revoked: bool,             // key compromised, never trusted again
     }
 
     impl<AccountId> RemoteAttestationDevice<AccountId> {
@@ -117,6 +327,107 @@ pub mod pallet {
         }
     }
 
+    /// Peer id rotation payload, signed by the attester key
+    #[derive(Clone, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct PeerRotationPayload<AccountId> {
+        pub servicer: AccountId,
+        pub attester_id: AttesterId,
+        pub old_peer_id: PeerId,
+        pub new_peer_id: PeerId,
+        pub nonce: u32, // prevents replaying an older rotation
+    }
+
+    /// Peer id rotation information (per attester)
+    #[derive(Clone, Default, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct PeerRotationInformation<BlockNumber> {
+        pub last_rotated_at: BlockNumber,
+        pub nonce: u32,
+    }
+
+    pub type AdvertisementEntryOf<T> = BoundedVec<u8, <T as Config>::MaxAdvertisementEntryLength>;
+
+    /// Service advertisement arguments, signed by the attester key
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ServiceAdvertisementArgs<T: Config> {
+        pub version: u32, // must always increase
+        pub endpoints: BoundedVec<AdvertisementEntryOf<T>, T::MaxAdvertisedEndpoints>,
+        pub protocols: BoundedVec<AdvertisementEntryOf<T>, T::MaxAdvertisedProtocols>,
+        pub region: RegionCode,
+        pub capacity: u64, // in bytes
+    }
+
+    /// Service advertisement (per attester)
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ServiceAdvertisement<T: Config> {
+        pub servicer: T::AccountId,
+        pub info: ServiceAdvertisementArgs<T>,
+        pub advertised_at: BlockNumberFor<T>,
+        pub expires_at: BlockNumberFor<T>,
+    }
+
+    impl<T: Config> ServiceAdvertisement<T> {
+        pub fn is_expired(&self, now: BlockNumberFor<T>) -> bool {
+            now >= self.expires_at
+        }
+    }
+
+    /// Servicers a mediator may vote on
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub enum MediatorScope<T: Config> {
+        All,
+        Servicers(frame_support::BoundedBTreeSet<T::AccountId, T::MaxMediatorScope>),
+    }
+
+    impl<T: Config> MediatorScope<T> {
+        pub fn contains(&self, servicer: &T::AccountId) -> bool {
+            match self {
+                | Self::All => true,
+                | Self::Servicers(servicers) => servicers.contains(servicer),
+            }
+        }
+    }
+
+    /// Mediator Information
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct MediatorInformation<T: Config> {
+        pub scope: MediatorScope<T>,
+        pub quota: u32, // reputation votes per window
+    }
+
+    /// Mediator votes spent in a window
+    #[derive(Clone, Default, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct MediatorUsage {
+        pub window: u32,
+        pub count: u32,
+    }
+
+    /// Heartbeat, signed by the attester key
+    #[derive(Clone, Eq, PartialEq)]
+    #[derive(codec::Decode, codec::Encode, codec::MaxEncodedLen)]
+    #[derive(sp_core::RuntimeDebug, scale_info::TypeInfo)]
+    pub struct Heartbeat {
+        pub attester_id: AttesterId,
+        pub window: u32,
+    }
+
     /// Servicer Information (Cooperatives)
     #[derive(Clone)]
     #[derive(codec::Decode, codec::Encode)]
@@ -125,6 +436,26 @@ pub mod pallet {
         pub rep_positive: u32,
         pub rep_negative: u32,
         pub bindings: UniqueMap<AttesterId, PeerId>,
+        pub inactive: UniqueSet<AttesterId>, // missed too many heartbeats
+    }
+
+    /// Cooperative Information, members of a servicer sharing its revenue
+    /// pro-rata to their shares
+    #[derive(CloneNoBound, EqNoBound, PartialEqNoBound)]
+    #[derive(codec::Decode, codec::Encode)]
+    #[derive(RuntimeDebugNoBound, scale_info::TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CooperativeInformation<T: Config> {
+        pub admin: T::AccountId, // manages membership
+        pub members: frame_support::BoundedBTreeMap<T::AccountId, u32, T::MaxCooperativeMembers>,
+    }
+
+    impl<T: Config> CooperativeInformation<T> {
+        pub fn total_shares(&self) -> u32 {
+            self.members
+                .values()
+                .fold(0u32, |total, shares| total.saturating_add(*shares))
+        }
     }
 
     impl ServicerInformation {
@@ -132,6 +463,10 @@ pub mod pallet {
             self.bindings.get(attester_id).copied()
         }
 
+        pub fn is_attester_active(&self, attester_id: &AttesterId) -> bool {
+            self.bindings.contains_key(attester_id) && !self.inactive.contains(attester_id)
+        }
+
         pub fn get_total_reputation(&self) -> i64 {
             (self.rep_positive as i64).saturating_sub(self.rep_negative as i64)
         }
@@ -150,12 +485,29 @@ pub mod pallet {
             Ok(())
         }
 
-        fn increase_reputation(&mut self) {
+        fn try_rotate_binding<T: Config>(
+            &mut self,
+            attester_id: &AttesterId,
+            new_peer_id: PeerId,
+        ) -> Result<PeerId, sp_runtime::DispatchError> {
+            let peer_id = self
+                .bindings
+                .get_mut(attester_id)
+                .ok_or(<Error<T>>::AttesterNotBindedToCaller)?;
+
+            if new_peer_id.eq(peer_id) {
+                return Err(<Error<T>>::PeerIdUnchanged.into());
+            }
+
+            Ok(sp_std::mem::replace(peer_id, new_peer_id))
+        }
+
+        pub(crate) fn increase_reputation(&mut self) {
             self.rep_positive = self.rep_positive.saturating_add(1);
         }
 
-        fn decrease_reputation(&mut self) {
-            self.rep_negative = self.rep_negative.saturating_sub(1);
+        pub(crate) fn decrease_reputation(&mut self) {
+            self.rep_negative = self.rep_negative.saturating_add(1);
         }
     }
 
@@ -179,6 +531,61 @@ pub mod pallet {
         MediatorAlreadyRegistered,
         /// Mediator is not registered
         MediatorNotFound,
+        /// Mediators exceeds runtime config
+        TooManyMediators,
+        /// Servicer is outside the mediator's scope
+        MediatorOutOfScope,
+        /// Mediator quota for the current window is exhausted
+        MediatorQuotaExceeded,
+        /// Mediator quota exceeds runtime config
+        MediatorQuotaTooHigh,
+        /// Mediator already voted on the attester in the current window
+        MediatorAlreadyVoted,
+        /// Attester is not binded to the caller
+        AttesterNotBindedToCaller,
+        /// Attester signature is invalid
+        InvalidAttesterSignature,
+        /// New peer id is the same as the current one
+        PeerIdUnchanged,
+        /// Peer id was rotated too recently
+        PeerRotationTooFrequent,
+        /// Attester device (guid & serial number) already supplied
+        AttesterDeviceAlreadySupplied,
+        /// Attester supply batch is empty
+        EmptySupplyBatch,
+        /// Service advertisement version must be greater than the current one
+        AdvertisementVersionTooLow,
+        /// Service advertisement must contain at least one endpoint
+        AdvertisementHasNoEndpoint,
+        /// Region code must be an uppercase ISO 3166-1 alpha-2 code
+        InvalidRegionCode,
+        /// Attester is revoked
+        AttesterRevoked,
+        /// Heartbeat is not for the current window
+        HeartbeatWindowMismatch,
+        /// Heartbeat already received for the current window
+        HeartbeatAlreadyReceived,
+        /// Attester transfer is not offered (to the caller)
+        AttesterTransferNotFound,
+        /// Attester cannot be transferred to its current big brother
+        AttesterTransferToSelf,
+        /// Attester can only be transferred to a council member
+        AttesterTransferReceiverNotBigBrother,
+        /// Supplying big brother is still a council member, force transfer
+        /// isn't allowed
+        AttesterSupplierStillBigBrother,
+        /// Servicer is not registered
+        ServicerNotFound,
+        /// Cooperative already exist
+        CooperativeAlreadyExist,
+        /// Cooperative not found
+        CooperativeNotFound,
+        /// Caller is not the cooperative admin
+        NotCooperativeAdmin,
+        /// Cooperative members exceeds runtime config
+        TooManyCooperativeMembers,
+        /// Cooperative must keep at least one member with shares
+        CooperativeWithoutShares,
     }
 
     #[pallet::event]
@@ -186,6 +593,8 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Big Brother's unbinded attestation device supplied
         BigBrotherAttesterSupplied { id: AttesterId, bb: T::AccountId },
+        /// Big Brother's batch of unbinded attestation devices supplied
+        BigBrotherAttestersBatchSupplied { bb: T::AccountId, count: u32 },
         /// Big Brother's unbinded attestation device recalled
         BigBrotherAttesterRecalled { id: AttesterId, bb: T::AccountId },
         /// Servicer's reputation increased
@@ -215,11 +624,20 @@ pub mod pallet {
         MediatorAdded { who: T::AccountId, by: T::AccountId },
         /// Mediator added
         MediatorRemoved { who: T::AccountId, by: T::AccountId },
+        /// Mediator scope or quota updated
+        MediatorUpdated { who: T::AccountId, by: T::AccountId },
         /// Servicer registration fee paid
         ServicerRegistrationFeePaid {
             who: T::AccountId,
             amount: BalanceCurrencyTypeOf<T>,
         },
+        /// Servicer registration fee portion distributed to the supplying big
+        /// brother
+        ServicerRegistrationFeeDistributed {
+            who: T::AccountId,
+            to: T::AccountId,
+            amount: BalanceCurrencyTypeOf<T>,
+        },
         /// Servicer's balance held for binding
         ServicerBalanceHeldForBinding {
             who: T::AccountId,
@@ -230,6 +648,68 @@ pub mod pallet {
             who: T::AccountId,
             amount: BalanceInspectTypeOf<T>,
         },
+        /// Attester's peer id rotated
+        PeerIdRotated {
+            who: T::AccountId,
+            which: AttesterId,
+            old_peer_id: PeerId,
+            new_peer_id: PeerId,
+        },
+        /// Attester revoked (compromised), attested files are flagged
+        AttesterRevoked {
+            id: AttesterId,
+            by: Option<T::AccountId>,
+        },
+        /// Attester transfer offered to another big brother
+        AttesterTransferOffered {
+            id: AttesterId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// Attester transfer offer cancelled
+        AttesterTransferCancelled { id: AttesterId, by: T::AccountId },
+        /// Attester transferred to another big brother, `by` is set on forced
+        /// transfers
+        AttesterTransferred {
+            id: AttesterId,
+            from: T::AccountId,
+            to: T::AccountId,
+            by: Option<T::AccountId>,
+        },
+        /// Attester's heartbeat received
+        HeartbeatReceived { which: AttesterId, window: u32 },
+        /// Attester marked inactive, it missed too many heartbeats
+        AttesterInactivated {
+            who: T::AccountId,
+            which: AttesterId,
+        },
+        /// Attester marked active again
+        AttesterReactivated {
+            who: T::AccountId,
+            which: AttesterId,
+        },
+        /// Servicer's service advertised (or refreshed)
+        ServiceAdvertised {
+            who: T::AccountId,
+            which: AttesterId,
+            version: u32,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// Servicer cooperative created, the servicer is its first member
+        CooperativeCreated { who: T::AccountId },
+        /// Cooperative member added, updated, or removed (zero shares)
+        CooperativeMemberSet {
+            who: T::AccountId,
+            member: T::AccountId,
+            shares: u32,
+        },
+        /// Cooperative admin changed
+        CooperativeAdminChanged {
+            who: T::AccountId,
+            admin: T::AccountId,
+        },
+        /// Cooperative dissolved, revenue goes to the servicer again
+        CooperativeDissolved { who: T::AccountId },
     }
 
     #[pallet::composite_enum]
@@ -241,6 +721,68 @@ pub mod pallet {
 
     // endregion
 
+    // region: Hooks
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::inactivate_missing_attesters(now, remaining_weight)
+        }
+
+        fn offchain_worker(now: BlockNumberFor<T>) {
+            let window = Self::heartbeat_window_of(now);
+
+            for attester_id in sp_io::crypto::ed25519_public_keys(ATTESTER_KEY_TYPE) {
+                Self::send_heartbeat(attester_id, window);
+            }
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let Call::sv_heartbeat {
+                heartbeat,
+                signature,
+            } = call
+            else {
+                return InvalidTransaction::Call.into();
+            };
+
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            if let Err(error) = Self::ensure_valid_heartbeat(heartbeat, signature) {
+                let stale_errors: [sp_runtime::DispatchError; 2] = [
+                    <Error<T>>::HeartbeatWindowMismatch.into(),
+                    <Error<T>>::HeartbeatAlreadyReceived.into(),
+                ];
+
+                return if stale_errors.contains(&error) {
+                    InvalidTransaction::Stale.into()
+                } else {
+                    InvalidTransaction::BadProof.into()
+                };
+            }
+
+            let window_length = T::HeartbeatWindow::get();
+            let window_left =
+                sp_runtime::traits::Saturating::saturating_sub(window_length, now % window_length);
+
+            ValidTransaction::with_tag_prefix("NagaraServicerHeartbeat")
+                .priority(T::HeartbeatPriority::get())
+                .and_provides((heartbeat.window, heartbeat.attester_id))
+                .longevity(sp_runtime::traits::SaturatedConversion::saturated_into(
+                    window_left,
+                ))
+                .propagate(true)
+                .build()
+        }
+    }
+
+    // endregion
+
     // region: Helper methods
 
     impl<T: Config> Pallet<T> {
@@ -249,13 +791,63 @@ pub mod pallet {
         ) -> Result<T::AccountId, sp_runtime::DispatchError> {
             let mediator = ensure_signed(origin)?;
 
-            if !<Mediators<T>>::get().contains(&mediator) {
+            if !<Mediators<T>>::contains_key(&mediator) {
                 return Err(<Error<T>>::MediatorNotFound.into());
             }
 
             Ok(mediator)
         }
 
+        fn mediator_window_of(block_number: BlockNumberFor<T>) -> u32 {
+            let window = block_number / T::MediatorWindow::get();
+
+            sp_runtime::traits::SaturatedConversion::saturated_into(window)
+        }
+
+        /// check the mediator's scope, quota & previous votes, then spend one
+        /// vote of the current window
+        fn try_spend_mediator_vote(
+            mediator: &T::AccountId,
+            on: AttesterId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let mediator_info = Self::mediators(mediator).ok_or(<Error<T>>::MediatorNotFound)?;
+            let attester = Self::attesters(on).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            let servicer = attester.binder.ok_or(<Error<T>>::AttesterIsUnbinded)?;
+            ensure!(
+                mediator_info.scope.contains(&servicer),
+                <Error<T>>::MediatorOutOfScope,
+            );
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let window = Self::mediator_window_of(now);
+            ensure!(
+                Self::mediator_votes(mediator, on) != Some(window),
+                <Error<T>>::MediatorAlreadyVoted,
+            );
+
+            let mut usage = Self::mediator_usages(mediator);
+
+            if usage.window != window {
+                // votes of the previous window are at most the quota
+                let _ =
+                    <MediatorVotes<T>>::clear_prefix(mediator, T::MaxMediatorQuota::get(), None);
+                usage = MediatorUsage {
+                    window,
+                    count: 0,
+                };
+            }
+
+            ensure!(
+                usage.count < mediator_info.quota,
+                <Error<T>>::MediatorQuotaExceeded,
+            );
+            usage.count = usage.count.saturating_add(1);
+            <MediatorUsages<T>>::insert(mediator, usage);
+            <MediatorVotes<T>>::insert(mediator, on, window);
+
+            Ok(())
+        }
+
         fn try_supply_new_attester(
             big_brother: T::AccountId,
             supply_args: RemoteAttestationDeviceSupplyArgs,
@@ -270,21 +862,69 @@ pub mod pallet {
                 return Err(<Error<T>>::AttesterAlreadySupplied.into());
             }
 
+            if <AttesterDevices<T>>::contains_key((guid, serial_number)) {
+                return Err(<Error<T>>::AttesterDeviceAlreadySupplied.into());
+            }
+
             let new_attester = RemoteAttestationDevice {
                 big_brother: big_brother.clone(),
                 binder: None,
                 guid,
                 serial_number,
+                revoked: false,
             };
             let event = Event::BigBrotherAttesterSupplied {
                 id,
                 bb: big_brother,
             };
             <Attesters<T>>::insert(id, new_attester);
+            <AttesterDevices<T>>::insert((guid, serial_number), id);
 
             Ok(event)
         }
 
+        fn try_supply_new_attesters(
+            big_brother: T::AccountId,
+            supply_args: sp_std::vec::Vec<RemoteAttestationDeviceSupplyArgs>,
+        ) -> Result<sp_std::vec::Vec<Event<T>>, sp_runtime::DispatchError> {
+            ensure!(!supply_args.is_empty(), <Error<T>>::EmptySupplyBatch);
+
+            let mut ids = UniqueSet::new();
+            let mut devices = UniqueSet::new();
+
+            // validate everything first, so the batch is all-or-nothing
+            for RemoteAttestationDeviceSupplyArgs {
+                id,
+                guid,
+                serial_number,
+            } in &supply_args
+            {
+                ensure!(
+                    ids.insert(*id) && !<Attesters<T>>::contains_key(id),
+                    <Error<T>>::AttesterAlreadySupplied,
+                );
+                ensure!(
+                    devices.insert((*guid, *serial_number))
+                        && !<AttesterDevices<T>>::contains_key((guid, serial_number)),
+                    <Error<T>>::AttesterDeviceAlreadySupplied,
+                );
+            }
+
+            let count = supply_args.len() as u32;
+            let mut events = sp_std::vec![];
+
+            for args in supply_args {
+                events.push(Self::try_supply_new_attester(big_brother.clone(), args)?);
+            }
+
+            events.push(Event::BigBrotherAttestersBatchSupplied {
+                bb: big_brother,
+                count,
+            });
+
+            Ok(events)
+        }
+
         fn try_recall_attester(
             caller: T::AccountId,
             attester_id: AttesterId,
@@ -299,11 +939,19 @@ pub mod pallet {
                 return Err(<Error<T>>::AttesterAlreadyBinded.into());
             }
 
+            // a revoked attester stays on the revocation list
+            if attester.revoked {
+                return Err(<Error<T>>::AttesterRevoked.into());
+            }
+
             if !caller.eq(&attester.big_brother) {
                 return Err(<Error<T>>::RestrictedCall.into());
             }
 
             <Attesters<T>>::remove(attester_id);
+            <AttesterDevices<T>>::remove((attester.guid, attester.serial_number));
+            <LastHeartbeats<T>>::remove(attester_id);
+            <AttesterTransfers<T>>::remove(attester_id);
             let event = Event::BigBrotherAttesterRecalled {
                 bb: caller,
                 id: attester_id,
@@ -350,7 +998,7 @@ pub mod pallet {
             if !<Servicers<T>>::contains_key(who) {
                 let amount = T::RegistrationFeeAmount::get();
                 let withdraw_reason = frame_support::traits::tokens::WithdrawReasons::FEE;
-                let _ = <<T as Config>::Currency as frame_support::traits::Currency<
+                let fee = <<T as Config>::Currency as frame_support::traits::Currency<
                     T::AccountId,
                 >>::withdraw(
                     who,
@@ -363,6 +1011,7 @@ pub mod pallet {
                     who: who.clone(),
                     amount,
                 });
+                events.push(Self::distribute_registration_fee(who, attester_id, fee));
             }
 
             <Servicers<T>>::try_mutate(who, |mutable_servicer| {
@@ -370,6 +1019,7 @@ pub mod pallet {
                     rep_positive: 0,
                     rep_negative: 0,
                     bindings: Default::default(),
+                    inactive: Default::default(),
                 });
                 mutable_servicer.try_add_binding::<T>(attester_id, peer_id)?;
 
@@ -379,6 +1029,29 @@ pub mod pallet {
             Ok(events)
         }
 
+        fn distribute_registration_fee(
+            who: &T::AccountId,
+            attester_id: AttesterId,
+            fee: NegativeImbalanceTypeOf<T>,
+        ) -> Event<T> {
+            let big_brother = Self::attesters(attester_id).unwrap().big_brother;
+            let total_fee = frame_support::traits::Imbalance::peek(&fee);
+            let bb_part = T::RegistrationFeeBigBrotherDistribution::get();
+            let (bb_fee, remainder) =
+                frame_support::traits::Imbalance::split(fee, bb_part.mul_floor(total_fee));
+            let amount = frame_support::traits::Imbalance::peek(&bb_fee);
+            <<T as Config>::Currency as frame_support::traits::Currency<T::AccountId>>::resolve_creating(&big_brother, bb_fee);
+            <T::OnRegistrationFee as frame_support::traits::OnUnbalanced<_>>::on_unbalanced(
+                remainder,
+            );
+
+            Event::ServicerRegistrationFeeDistributed {
+                who: who.clone(),
+                to: big_brother,
+                amount,
+            }
+        }
+
         fn try_bind_attester(
             binder: T::AccountId,
             peer_id: PeerId,
@@ -394,6 +1067,10 @@ pub mod pallet {
                 return Err(<Error<T>>::AttesterAlreadyBinded.into());
             }
 
+            if attester.revoked {
+                return Err(<Error<T>>::AttesterRevoked.into());
+            }
+
             let mut events = sp_std::vec![];
 
             <Attesters<T>>::try_mutate(attester_id, |mutable_attester| {
@@ -414,6 +1091,8 @@ pub mod pallet {
 
                 let mutable_attester = mutable_attester.as_mut().unwrap();
                 mutable_attester.binder = Some(binder.clone());
+                let now = <frame_system::Pallet<T>>::block_number();
+                <LastHeartbeats<T>>::insert(attester_id, Self::heartbeat_window_of(now));
 
                 Result::<(), sp_runtime::DispatchError>::Ok(())
             })?;
@@ -427,52 +1106,570 @@ pub mod pallet {
             Ok(events)
         }
 
-        pub fn rep_increase_by_attester_id(
-            by: T::AccountId,
-            on: AttesterId,
+        fn ensure_attester_signature<P: codec::Encode>(
+            attester_id: &AttesterId,
+            context: [u8; 4],
+            payload: &P,
+            signature: &AttesterSignature,
         ) -> Result<(), sp_runtime::DispatchError> {
+            let message = attester_signing_message(context, payload);
             ensure!(
-                <Attesters<T>>::contains_key(on),
-                <Error<T>>::AttesterDoesntExist,
+                sp_runtime::traits::Verify::verify(signature, &message[..], attester_id),
+                <Error<T>>::InvalidAttesterSignature,
             );
-            let attester = <Attesters<T>>::get(on).unwrap();
-            ensure!(attester.binder.is_some(), <Error<T>>::AttesterIsUnbinded);
-            let who = attester.binder.unwrap();
-            <Servicers<T>>::mutate(&who, |servicers_mut| {
-                servicers_mut.as_mut().unwrap().increase_reputation()
-            });
-            Self::deposit_event(Event::ServicerReputationIncreased {
-                who,
-                on,
-                by,
-            });
 
             Ok(())
         }
 
-        pub fn rep_decrease_by_attester_id(
-            by: T::AccountId,
-            on: AttesterId,
-        ) -> Result<(), sp_runtime::DispatchError> {
+        fn try_rotate_peer_id(
+            servicer: T::AccountId,
+            attester_id: AttesterId,
+            new_peer_id: PeerId,
+            signature: AttesterSignature,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
             ensure!(
-                <Attesters<T>>::contains_key(on),
-                <Error<T>>::AttesterDoesntExist,
+                attester.binder.as_ref() == Some(&servicer),
+                <Error<T>>::AttesterNotBindedToCaller,
             );
-            let attester = <Attesters<T>>::get(on).unwrap();
-            ensure!(attester.binder.is_some(), <Error<T>>::AttesterIsUnbinded);
-            let who = attester.binder.unwrap();
-            <Servicers<T>>::mutate(&who, |servicers_mut| {
-                servicers_mut.as_mut().unwrap().decrease_reputation()
-            });
-            Self::deposit_event(Event::ServicerReputationDecreased {
-                who,
-                on,
-                by,
-            });
+            ensure!(!attester.revoked, <Error<T>>::AttesterRevoked);
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let rotation = Self::peer_rotations(attester_id);
+
+            if let Some(PeerRotationInformation {
+                last_rotated_at, ..
+            }) = rotation.as_ref()
+            {
+                let cooldown_until = sp_runtime::traits::Saturating::saturating_add(
+                    *last_rotated_at,
+                    T::PeerRotationCooldown::get(),
+                );
+                ensure!(now >= cooldown_until, <Error<T>>::PeerRotationTooFrequent);
+            }
 
-            Ok(())
-        }
-    }
+            let nonce = rotation.map(|rotation| rotation.nonce).unwrap_or_default();
+            let old_peer_id = <Servicers<T>>::try_mutate(&servicer, |mutable_servicer| {
+                let mutable_servicer = mutable_servicer
+                    .as_mut()
+                    .ok_or(<Error<T>>::AttesterNotBindedToCaller)?;
+                let old_peer_id = mutable_servicer
+                    .get_peer_id(&attester_id)
+                    .ok_or(<Error<T>>::AttesterNotBindedToCaller)?;
+                let payload = PeerRotationPayload {
+                    servicer: servicer.clone(),
+                    attester_id,
+                    old_peer_id,
+                    new_peer_id,
+                    nonce,
+                };
+                Self::ensure_attester_signature(
+                    &attester_id,
+                    SIGNING_CONTEXT_PEER_ROTATION,
+                    &payload,
+                    &signature,
+                )?;
+                mutable_servicer.try_rotate_binding::<T>(&attester_id, new_peer_id)
+            })?;
+            <PeerRotations<T>>::insert(
+                attester_id,
+                PeerRotationInformation {
+                    last_rotated_at: now,
+                    nonce: nonce.saturating_add(1),
+                },
+            );
+
+            Ok(Event::PeerIdRotated {
+                who: servicer,
+                which: attester_id,
+                old_peer_id,
+                new_peer_id,
+            })
+        }
+
+        fn try_advertise_service(
+            servicer: T::AccountId,
+            attester_id: AttesterId,
+            args: ServiceAdvertisementArgs<T>,
+            signature: AttesterSignature,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            ensure!(
+                attester.binder.as_ref() == Some(&servicer),
+                <Error<T>>::AttesterNotBindedToCaller,
+            );
+            ensure!(!attester.revoked, <Error<T>>::AttesterRevoked);
+            ensure!(
+                !args.endpoints.is_empty(),
+                <Error<T>>::AdvertisementHasNoEndpoint
+            );
+            ensure!(
+                args.region.iter().all(u8::is_ascii_uppercase),
+                <Error<T>>::InvalidRegionCode,
+            );
+
+            if let Some(previous) = Self::advertisements(attester_id) {
+                ensure!(
+                    args.version > previous.info.version,
+                    <Error<T>>::AdvertisementVersionTooLow,
+                );
+            }
+
+            Self::ensure_attester_signature(
+                &attester_id,
+                SIGNING_CONTEXT_ADVERTISEMENT,
+                &(&servicer, &attester_id, &args),
+                &signature,
+            )?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let expires_at =
+                sp_runtime::traits::Saturating::saturating_add(now, T::AdvertisementTtl::get());
+            let version = args.version;
+            <Advertisements<T>>::insert(
+                attester_id,
+                ServiceAdvertisement {
+                    servicer: servicer.clone(),
+                    info: args,
+                    advertised_at: now,
+                    expires_at,
+                },
+            );
+
+            Ok(Event::ServiceAdvertised {
+                who: servicer,
+                which: attester_id,
+                version,
+                expires_at,
+            })
+        }
+
+        fn try_revoke_attester(
+            by: Option<T::AccountId>,
+            attester_id: AttesterId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            <Attesters<T>>::try_mutate(attester_id, |mutable_attester| {
+                let mutable_attester = mutable_attester
+                    .as_mut()
+                    .ok_or(<Error<T>>::AttesterDoesntExist)?;
+                ensure!(!mutable_attester.revoked, <Error<T>>::AttesterRevoked);
+                mutable_attester.revoked = true;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            T::OnAttesterRevoked::on_attester_revoked(&attester_id);
+
+            Ok(Event::AttesterRevoked {
+                id: attester_id,
+                by,
+            })
+        }
+
+        fn try_offer_attester_transfer(
+            big_brother: T::AccountId,
+            attester_id: AttesterId,
+            to: T::AccountId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            ensure!(
+                attester.big_brother.eq(&big_brother),
+                <Error<T>>::RestrictedCall,
+            );
+            ensure!(!big_brother.eq(&to), <Error<T>>::AttesterTransferToSelf);
+            ensure!(
+                ngr_bbcm::Pallet::<T>::is_council_member(&to),
+                <Error<T>>::AttesterTransferReceiverNotBigBrother,
+            );
+            <AttesterTransfers<T>>::insert(attester_id, to.clone());
+
+            Ok(Event::AttesterTransferOffered {
+                id: attester_id,
+                from: big_brother,
+                to,
+            })
+        }
+
+        fn try_cancel_attester_transfer(
+            big_brother: T::AccountId,
+            attester_id: AttesterId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            ensure!(
+                attester.big_brother.eq(&big_brother),
+                <Error<T>>::RestrictedCall,
+            );
+            ensure!(
+                <AttesterTransfers<T>>::contains_key(attester_id),
+                <Error<T>>::AttesterTransferNotFound,
+            );
+            <AttesterTransfers<T>>::remove(attester_id);
+
+            Ok(Event::AttesterTransferCancelled {
+                id: attester_id,
+                by: big_brother,
+            })
+        }
+
+        fn try_transfer_attester(
+            by: Option<T::AccountId>,
+            attester_id: AttesterId,
+            to: T::AccountId,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            let from = <Attesters<T>>::try_mutate(attester_id, |mutable_attester| {
+                let mutable_attester = mutable_attester
+                    .as_mut()
+                    .ok_or(<Error<T>>::AttesterDoesntExist)?;
+                ensure!(
+                    !mutable_attester.big_brother.eq(&to),
+                    <Error<T>>::AttesterTransferToSelf,
+                );
+                let from = sp_std::mem::replace(&mut mutable_attester.big_brother, to.clone());
+
+                Result::<T::AccountId, sp_runtime::DispatchError>::Ok(from)
+            })?;
+            <AttesterTransfers<T>>::remove(attester_id);
+
+            Ok(Event::AttesterTransferred {
+                id: attester_id,
+                from,
+                to,
+                by,
+            })
+        }
+
+        pub(crate) fn heartbeat_window_of(block_number: BlockNumberFor<T>) -> u32 {
+            let window = block_number / T::HeartbeatWindow::get();
+
+            sp_runtime::traits::SaturatedConversion::saturated_into(window)
+        }
+
+        fn ensure_heartbeat_is_due(
+            attester_id: &AttesterId,
+            window: u32,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                window == Self::heartbeat_window_of(now),
+                <Error<T>>::HeartbeatWindowMismatch,
+            );
+            ensure!(
+                Self::last_heartbeats(attester_id) != Some(window),
+                <Error<T>>::HeartbeatAlreadyReceived,
+            );
+
+            Ok(())
+        }
+
+        fn ensure_valid_heartbeat(
+            heartbeat: &Heartbeat,
+            signature: &AttesterSignature,
+        ) -> Result<T::AccountId, sp_runtime::DispatchError> {
+            let Heartbeat {
+                attester_id,
+                window,
+            } = heartbeat;
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            let servicer = attester.binder.ok_or(<Error<T>>::AttesterIsUnbinded)?;
+            ensure!(!attester.revoked, <Error<T>>::AttesterRevoked);
+            Self::ensure_heartbeat_is_due(attester_id, *window)?;
+            Self::ensure_attester_signature(
+                attester_id,
+                SIGNING_CONTEXT_HEARTBEAT,
+                heartbeat,
+                signature,
+            )?;
+
+            Ok(servicer)
+        }
+
+        fn try_receive_heartbeat(
+            heartbeat: Heartbeat,
+            signature: AttesterSignature,
+        ) -> Result<sp_std::vec::Vec<Event<T>>, sp_runtime::DispatchError> {
+            let servicer = Self::ensure_valid_heartbeat(&heartbeat, &signature)?;
+            let Heartbeat {
+                attester_id,
+                window,
+            } = heartbeat;
+            let mut events = sp_std::vec![];
+            <LastHeartbeats<T>>::insert(attester_id, window);
+            events.push(Event::HeartbeatReceived {
+                which: attester_id,
+                window,
+            });
+
+            <Servicers<T>>::mutate(&servicer, |mutable_servicer| {
+                if let Some(mutable_servicer) = mutable_servicer.as_mut() {
+                    if mutable_servicer.inactive.remove(&attester_id) {
+                        events.push(Event::AttesterReactivated {
+                            who: servicer.clone(),
+                            which: attester_id,
+                        });
+                    }
+                }
+            });
+
+            Ok(events)
+        }
+
+        /// (offchain) sign & submit a heartbeat with a local attester key
+        fn send_heartbeat(attester_id: AttesterId, window: u32) {
+            let storage_key = codec::Encode::encode(&(PALLET_IDENTIFICATION, attester_id));
+            let storage = sp_runtime::offchain::storage::StorageValueRef::persistent(&storage_key);
+
+            if let Ok(Some(last_window)) = storage.get::<u32>() {
+                if last_window >= window {
+                    return;
+                }
+            }
+
+            match Self::attesters(attester_id) {
+                | Some(attester) if attester.is_binded() && !attester.revoked => {},
+                | _ => return,
+            }
+
+            if Self::ensure_heartbeat_is_due(&attester_id, window).is_err() {
+                return;
+            }
+
+            let heartbeat = Heartbeat {
+                attester_id,
+                window,
+            };
+            let message = attester_signing_message(SIGNING_CONTEXT_HEARTBEAT, &heartbeat);
+            let Some(signature) =
+                sp_io::crypto::ed25519_sign(ATTESTER_KEY_TYPE, &attester_id, &message)
+            else {
+                return;
+            };
+            let call = Call::sv_heartbeat {
+                heartbeat,
+                signature,
+            };
+            let submission = frame_system::offchain::SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+
+            if submission.is_ok() {
+                storage.set(&window);
+            }
+        }
+
+        /// mark attesters which missed too many heartbeat windows as inactive,
+        /// servicers are checked round-robin, bounded by weight & per block
+        /// limit
+        fn inactivate_missing_attesters(
+            now: BlockNumberFor<T>,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let weight_per_servicer = db_weight.reads_writes(8, 1); // TODO: please benchmark
+            let mut consumed_weight = db_weight.reads_writes(2, 1); // cursor & next servicer
+
+            if remaining_weight.any_lt(consumed_weight.saturating_add(weight_per_servicer)) {
+                return Weight::zero();
+            }
+
+            let max_servicers = T::MaxHeartbeatChecksPerBlock::get() as usize;
+            let mut servicers = sp_std::vec::Vec::new();
+            let mut batch_full = false;
+            let sweep = match <HeartbeatSweepCursor<T>>::get() {
+                | Some(cursor) => <Servicers<T>>::iter_from(cursor),
+                | None => <Servicers<T>>::iter(),
+            };
+
+            for (servicer, servicer_info) in sweep {
+                // one last heartbeat read per binding
+                let weight = weight_per_servicer
+                    .saturating_add(db_weight.reads(servicer_info.bindings.len() as u64));
+
+                if servicers.len() >= max_servicers
+                    || remaining_weight.any_lt(consumed_weight.saturating_add(weight))
+                {
+                    batch_full = true;
+                    break;
+                }
+
+                consumed_weight = consumed_weight.saturating_add(weight);
+                servicers.push((servicer, servicer_info));
+            }
+
+            match servicers.last() {
+                | Some((servicer, _)) if batch_full => {
+                    <HeartbeatSweepCursor<T>>::put(<Servicers<T>>::hashed_key_for(servicer));
+                },
+                | None if batch_full => {}, // next servicer doesn't fit, retried next time
+                | _ => <HeartbeatSweepCursor<T>>::kill(), // sweep done, restart next time
+            }
+
+            let window = Self::heartbeat_window_of(now);
+            let max_missed = T::MaxMissedHeartbeats::get();
+
+            for (servicer, mut servicer_info) in servicers {
+                let missing_attester_ids = servicer_info
+                    .bindings
+                    .keys()
+                    .filter(|attester_id| !servicer_info.inactive.contains(attester_id))
+                    .filter(|attester_id| {
+                        let last_window = Self::last_heartbeats(attester_id).unwrap_or_default();
+
+                        window.saturating_sub(last_window) > max_missed
+                    })
+                    .copied()
+                    .collect::<sp_std::vec::Vec<_>>();
+
+                if missing_attester_ids.is_empty() {
+                    continue;
+                }
+
+                for attester_id in missing_attester_ids {
+                    servicer_info.inactive.insert(attester_id);
+                    Self::deposit_event(Event::AttesterInactivated {
+                        who: servicer.clone(),
+                        which: attester_id,
+                    });
+                }
+
+                <Servicers<T>>::insert(&servicer, servicer_info);
+            }
+
+            consumed_weight
+        }
+
+        /// check whether an attester is binded, not revoked, and still sending
+        /// heartbeats
+        pub fn is_attester_active(attester_id: &AttesterId) -> bool {
+            let Some(attester) = Self::attesters(attester_id) else {
+                return false;
+            };
+
+            if attester.revoked {
+                return false;
+            }
+
+            attester
+                .binder
+                .and_then(Self::servicers)
+                .map(|servicer| servicer.is_attester_active(attester_id))
+                .unwrap_or_default()
+        }
+
+        /// check whether an attester is revoked
+        pub fn is_attester_revoked(attester_id: &AttesterId) -> bool {
+            Self::attesters(attester_id)
+                .map(|attester| attester.revoked)
+                .unwrap_or_default()
+        }
+
+        fn ensure_cooperative_admin(
+            caller: &T::AccountId,
+            servicer: &T::AccountId,
+        ) -> Result<CooperativeInformation<T>, sp_runtime::DispatchError> {
+            let cooperative =
+                Self::cooperatives(servicer).ok_or(<Error<T>>::CooperativeNotFound)?;
+            ensure!(
+                cooperative.admin.eq(caller),
+                <Error<T>>::NotCooperativeAdmin,
+            );
+
+            Ok(cooperative)
+        }
+
+        fn try_set_cooperative_member(
+            servicer: T::AccountId,
+            member: T::AccountId,
+            shares: u32,
+        ) -> Result<Event<T>, sp_runtime::DispatchError> {
+            <Cooperatives<T>>::try_mutate(&servicer, |mutable_cooperative| {
+                let mutable_cooperative = mutable_cooperative
+                    .as_mut()
+                    .ok_or(<Error<T>>::CooperativeNotFound)?;
+
+                if shares == 0 {
+                    mutable_cooperative.members.remove(&member);
+                } else {
+                    mutable_cooperative
+                        .members
+                        .try_insert(member.clone(), shares)
+                        .map_err(|_| <Error<T>>::TooManyCooperativeMembers)?;
+                }
+
+                ensure!(
+                    mutable_cooperative.total_shares() > 0,
+                    <Error<T>>::CooperativeWithoutShares,
+                );
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+
+            Ok(Event::CooperativeMemberSet {
+                who: servicer,
+                member,
+                shares,
+            })
+        }
+
+        /// revenue recipients of a servicer with their shares, the servicer
+        /// itself unless it is a cooperative
+        pub fn revenue_shares(servicer: &T::AccountId) -> sp_std::vec::Vec<(T::AccountId, u32)> {
+            match Self::cooperatives(servicer) {
+                | Some(cooperative) => cooperative.members.into_iter().collect(),
+                | None => sp_std::vec![(servicer.clone(), 1)],
+            }
+        }
+
+        /// get the service advertisement of an attester, unless expired
+        pub fn active_advertisement(attester_id: &AttesterId) -> Option<ServiceAdvertisement<T>> {
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            Self::advertisements(attester_id).filter(|advertisement| !advertisement.is_expired(now))
+        }
+
+        pub fn rep_increase_by_attester_id(
+            by: T::AccountId,
+            on: AttesterId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(
+                <Attesters<T>>::contains_key(on),
+                <Error<T>>::AttesterDoesntExist,
+            );
+            let attester = <Attesters<T>>::get(on).unwrap();
+            ensure!(attester.binder.is_some(), <Error<T>>::AttesterIsUnbinded);
+            ensure!(!attester.revoked, <Error<T>>::AttesterRevoked);
+            let who = attester.binder.unwrap();
+            <Servicers<T>>::mutate(&who, |servicers_mut| {
+                servicers_mut.as_mut().unwrap().increase_reputation()
+            });
+            Self::deposit_event(Event::ServicerReputationIncreased {
+                who,
+                on,
+                by,
+            });
+
+            Ok(())
+        }
+
+        pub fn rep_decrease_by_attester_id(
+            by: T::AccountId,
+            on: AttesterId,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(
+                <Attesters<T>>::contains_key(on),
+                <Error<T>>::AttesterDoesntExist,
+            );
+            let attester = <Attesters<T>>::get(on).unwrap();
+            ensure!(attester.binder.is_some(), <Error<T>>::AttesterIsUnbinded);
+            let who = attester.binder.unwrap();
+            <Servicers<T>>::mutate(&who, |servicers_mut| {
+                servicers_mut.as_mut().unwrap().decrease_reputation()
+            });
+            Self::deposit_event(Event::ServicerReputationDecreased {
+                who,
+                on,
+                by,
+            });
+
+            Ok(())
+        }
+    }
 
     // endregion
 
@@ -501,14 +1698,30 @@ pub mod pallet {
         pub fn bb_mediator_add(
             origin: OriginFor<T>,
             who: T::AccountId,
+            scope: MediatorScope<T>,
+            quota: u32,
         ) -> DispatchResultWithPostInfo {
             let by = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
             ngr_bbcm::Pallet::<T>::ensure_account_has_verified_legality(&who)?;
             ensure!(
-                !<Mediators<T>>::get().contains(&who),
+                !<Mediators<T>>::contains_key(&who),
                 <Error<T>>::MediatorAlreadyRegistered,
             );
-            let _ = <Mediators<T>>::mutate(|mediators_mut| mediators_mut.try_insert(who.clone()));
+            ensure!(
+                quota <= T::MaxMediatorQuota::get(),
+                <Error<T>>::MediatorQuotaTooHigh,
+            );
+            ensure!(
+                <Mediators<T>>::count() < T::MaxMediators::get(),
+                <Error<T>>::TooManyMediators,
+            );
+            <Mediators<T>>::insert(
+                &who,
+                MediatorInformation {
+                    scope,
+                    quota,
+                },
+            );
             Self::deposit_event(Event::MediatorAdded {
                 who,
                 by,
@@ -519,17 +1732,22 @@ pub mod pallet {
 
         /// Big Brother: Remove a mediator
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        #[pallet::weight(
+            Weight::from_parts(0, 8192)
+                .saturating_add(T::DbWeight::get().writes(T::MaxMediatorQuota::get().into()))
+        )] // TODO: please benchmark
         pub fn bb_mediator_remove(
             origin: OriginFor<T>,
             who: T::AccountId,
         ) -> DispatchResultWithPostInfo {
             let by = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
             ensure!(
-                <Mediators<T>>::get().contains(&who),
+                <Mediators<T>>::contains_key(&who),
                 <Error<T>>::MediatorNotFound,
             );
-            let _ = <Mediators<T>>::mutate(|mediators_mut| mediators_mut.remove(&who));
+            <Mediators<T>>::remove(&who);
+            <MediatorUsages<T>>::remove(&who);
+            let _ = <MediatorVotes<T>>::clear_prefix(&who, T::MaxMediatorQuota::get(), None);
             Self::deposit_event(Event::MediatorRemoved {
                 who,
                 by,
@@ -572,9 +1790,13 @@ pub mod pallet {
 
         /// Mediators: Increase servicer reputation
         #[pallet::call_index(5)]
-        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        #[pallet::weight(
+            Weight::from_parts(0, 8192)
+                .saturating_add(T::DbWeight::get().writes(T::MaxMediatorQuota::get().into()))
+        )] // TODO: please benchmark
         pub fn md_rep_increase(origin: OriginFor<T>, on: AttesterId) -> DispatchResultWithPostInfo {
             let by = Self::ensure_and_get_signed_mediator(origin)?;
+            Self::try_spend_mediator_vote(&by, on)?;
             Self::rep_increase_by_attester_id(by, on)?;
 
             Ok(Pays::No.into())
@@ -582,13 +1804,307 @@ pub mod pallet {
 
         /// Mediators: decrease servicer reputation
         #[pallet::call_index(6)]
-        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        #[pallet::weight(
+            Weight::from_parts(0, 8192)
+                .saturating_add(T::DbWeight::get().writes(T::MaxMediatorQuota::get().into()))
+        )] // TODO: please benchmark
         pub fn md_rep_decrease(origin: OriginFor<T>, on: AttesterId) -> DispatchResultWithPostInfo {
             let by = Self::ensure_and_get_signed_mediator(origin)?;
+            Self::try_spend_mediator_vote(&by, on)?;
             Self::rep_decrease_by_attester_id(by, on)?;
 
             Ok(Pays::No.into())
         }
+
+        /// Servicer: Rotate the peer id of a binded attester, the rotation must
+        /// also be signed by the attester key
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn sv_peer_rotate(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+            new_peer_id: PeerId,
+            signature: AttesterSignature,
+        ) -> DispatchResultWithPostInfo {
+            let servicer = ensure_signed(origin)?;
+            let event = Self::try_rotate_peer_id(servicer, attester_id, new_peer_id, signature)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Big Brother: Supply a batch of Attesters (all-or-nothing)
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(0, 8192).saturating_mul(supply_args.len() as u64))] // TODO: please benchmark
+        pub fn bb_attester_supply_batch(
+            origin: OriginFor<T>,
+            supply_args: BoundedVec<RemoteAttestationDeviceSupplyArgs, T::MaxAttesterSupplyBatch>,
+        ) -> DispatchResultWithPostInfo {
+            let big_brother = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
+            let events = Self::try_supply_new_attesters(big_brother, supply_args.into_inner())?;
+
+            for event in events {
+                Self::deposit_event(event);
+            }
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Servicer: Advertise (or refresh) the service of a binded attester,
+        /// the advertisement must also be signed by the attester key
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn sv_advertise(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+            args: ServiceAdvertisementArgs<T>,
+            signature: AttesterSignature,
+        ) -> DispatchResultWithPostInfo {
+            let servicer = ensure_signed(origin)?;
+            let event = Self::try_advertise_service(servicer, attester_id, args, signature)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Attester: Heartbeat (unsigned, signed by the attester key)
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn sv_heartbeat(
+            origin: OriginFor<T>,
+            heartbeat: Heartbeat,
+            signature: AttesterSignature,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let events = Self::try_receive_heartbeat(heartbeat, signature)?;
+
+            for event in events {
+                Self::deposit_event(event);
+            }
+
+            Ok(Pays::No.into())
+        }
+
+        /// Big Brother: Revoke a (compromised) attester supplied by the caller
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn bb_attester_revoke(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            let big_brother = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            ensure!(
+                attester.big_brother.eq(&big_brother),
+                <Error<T>>::RestrictedCall,
+            );
+            let event = Self::try_revoke_attester(Some(big_brother), attester_id)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Sudo or Elder: Revoke a (compromised) attester
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn se_attester_revoke(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            let by = ngr_bbcm::Pallet::<T>::ensure_and_get_elder_or_root(origin)?;
+            let event = Self::try_revoke_attester(by, attester_id)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Big Brother: Update a mediator's scope & quota
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn bb_mediator_update(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            scope: MediatorScope<T>,
+            quota: u32,
+        ) -> DispatchResultWithPostInfo {
+            let by = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
+            ensure!(
+                quota <= T::MaxMediatorQuota::get(),
+                <Error<T>>::MediatorQuotaTooHigh,
+            );
+            <Mediators<T>>::try_mutate(&who, |mediator_mut| {
+                let mediator_mut = mediator_mut.as_mut().ok_or(<Error<T>>::MediatorNotFound)?;
+                mediator_mut.scope = scope;
+                mediator_mut.quota = quota;
+
+                Result::<(), sp_runtime::DispatchError>::Ok(())
+            })?;
+            Self::deposit_event(Event::MediatorUpdated {
+                who,
+                by,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Big Brother: Offer an attester supplied by the caller to another big
+        /// brother
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn bb_attester_transfer_offer(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+            to: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let big_brother = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
+            let event = Self::try_offer_attester_transfer(big_brother, attester_id, to)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Big Brother: Cancel an attester transfer offer
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn bb_attester_transfer_cancel(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            let big_brother = ensure_signed(origin)?; // might have left the council
+            let event = Self::try_cancel_attester_transfer(big_brother, attester_id)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Big Brother: Accept an attester transfer offered to the caller
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn bb_attester_transfer_accept(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+        ) -> DispatchResultWithPostInfo {
+            let big_brother = ngr_bbcm::Pallet::<T>::ensure_and_get_council_member(origin)?;
+            ensure!(
+                Self::attester_transfers(attester_id).as_ref() == Some(&big_brother),
+                <Error<T>>::AttesterTransferNotFound,
+            );
+            let event = Self::try_transfer_attester(None, attester_id, big_brother)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Sudo or Elder: Force transfer an attester whose supplying big
+        /// brother has left the council
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn se_attester_transfer_force(
+            origin: OriginFor<T>,
+            attester_id: AttesterId,
+            to: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let by = ngr_bbcm::Pallet::<T>::ensure_and_get_elder_or_root(origin)?;
+            let attester = Self::attesters(attester_id).ok_or(<Error<T>>::AttesterDoesntExist)?;
+            ensure!(
+                !ngr_bbcm::Pallet::<T>::is_council_member(&attester.big_brother),
+                <Error<T>>::AttesterSupplierStillBigBrother,
+            );
+            ensure!(
+                ngr_bbcm::Pallet::<T>::is_council_member(&to),
+                <Error<T>>::AttesterTransferReceiverNotBigBrother,
+            );
+            let event = Self::try_transfer_attester(by, attester_id, to)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Servicer: Turn the caller into a cooperative, the caller becomes its
+        /// admin & sole member
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn sv_cooperative_create(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let servicer = ensure_signed(origin)?;
+            ensure!(
+                <Servicers<T>>::contains_key(&servicer),
+                <Error<T>>::ServicerNotFound,
+            );
+            ensure!(
+                !<Cooperatives<T>>::contains_key(&servicer),
+                <Error<T>>::CooperativeAlreadyExist,
+            );
+            let mut members = frame_support::BoundedBTreeMap::new();
+            members
+                .try_insert(servicer.clone(), 1)
+                .map_err(|_| <Error<T>>::TooManyCooperativeMembers)?;
+            <Cooperatives<T>>::insert(
+                &servicer,
+                CooperativeInformation {
+                    admin: servicer.clone(),
+                    members,
+                },
+            );
+            Self::deposit_event(Event::CooperativeCreated {
+                who: servicer,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Cooperative Admin: Add, update, or remove (zero shares) a member
+        #[pallet::call_index(19)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn co_member_set(
+            origin: OriginFor<T>,
+            servicer: T::AccountId,
+            member: T::AccountId,
+            shares: u32,
+        ) -> DispatchResultWithPostInfo {
+            let admin = ensure_signed(origin)?;
+            Self::ensure_cooperative_admin(&admin, &servicer)?;
+            let event = Self::try_set_cooperative_member(servicer, member, shares)?;
+            Self::deposit_event(event);
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Cooperative Admin: Hand over the admin role
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn co_admin_set(
+            origin: OriginFor<T>,
+            servicer: T::AccountId,
+            new_admin: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let admin = ensure_signed(origin)?;
+            let mut cooperative = Self::ensure_cooperative_admin(&admin, &servicer)?;
+            cooperative.admin = new_admin.clone();
+            <Cooperatives<T>>::insert(&servicer, cooperative);
+            Self::deposit_event(Event::CooperativeAdminChanged {
+                who: servicer,
+                admin: new_admin,
+            });
+
+            Ok(Pays::Yes.into())
+        }
+
+        /// Cooperative Admin: Dissolve the cooperative
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(0, 8192))] // TODO: please benchmark
+        pub fn co_dissolve(
+            origin: OriginFor<T>,
+            servicer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let admin = ensure_signed(origin)?;
+            Self::ensure_cooperative_admin(&admin, &servicer)?;
+            <Cooperatives<T>>::remove(&servicer);
+            Self::deposit_event(Event::CooperativeDissolved {
+                who: servicer,
+            });
+
+            Ok(Pays::Yes.into())
+        }
     }
 
     // endregion
@@ -0,0 +1,163 @@
+//! Storage migrations for the Servicer Registry pallet
+
+pub mod v1 {
+    use crate::*;
+    use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use frame_support::weights::Weight;
+
+    mod old {
+        use crate::*;
+        use frame_support::pallet_prelude::{StorageValue, ValueQuery};
+
+        /// Mediators before v1 (a set, without scopes & quotas)
+        #[frame_support::storage_alias]
+        pub type Mediators<T: Config> = StorageValue<
+            Pallet<T>,
+            frame_support::BoundedBTreeSet<AccountTypeOf<T>, <T as Config>::MaxMediators>,
+            ValueQuery,
+        >;
+    }
+
+    /// Remote Attestation Device before v1 (without revocation)
+    #[derive(codec::Decode, codec::Encode)]
+    struct OldRemoteAttestationDevice<AccountId> {
+        big_brother: AccountId,
+        serial_number: u32,
+        guid: Guid,
+        binder: Option<AccountId>,
+    }
+
+    /// Servicer Information before v1 (without heartbeats)
+    #[derive(codec::Decode, codec::Encode)]
+    struct OldServicerInformation {
+        rep_positive: u32,
+        rep_negative: u32,
+        bindings: UniqueMap<AttesterId, PeerId>,
+    }
+
+    /// Migrates the registry storage from the unversioned layout to v1
+    pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+            if on_chain_version >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            weight = weight.saturating_add(translate_attesters::<T>());
+            weight = weight.saturating_add(backfill_attester_devices::<T>());
+            weight = weight.saturating_add(translate_servicers::<T>());
+            weight = weight.saturating_add(seed_last_heartbeats::<T>());
+            weight = weight.saturating_add(move_mediators::<T>());
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            weight
+        }
+    }
+
+    /// existing attesters are not revoked
+    fn translate_attesters<T: Config>() -> Weight {
+        let mut translated = 0u64;
+
+        <Attesters<T>>::translate::<OldRemoteAttestationDevice<T::AccountId>, _>(|_, old| {
+            translated = translated.saturating_add(1);
+
+            Some(RemoteAttestationDevice {
+                big_brother: old.big_brother,
+                serial_number: old.serial_number,
+                guid: old.guid,
+                binder: old.binder,
+                revoked: false,
+            })
+        });
+
+        T::DbWeight::get().reads_writes(translated, translated)
+    }
+
+    /// index the (guid, serial number) devices of the existing attesters
+    fn backfill_attester_devices<T: Config>() -> Weight {
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+
+        for (attester_id, attester) in <Attesters<T>>::iter() {
+            reads = reads.saturating_add(2);
+
+            let device = (attester.guid, attester.serial_number);
+
+            if !<AttesterDevices<T>>::contains_key(device) {
+                <AttesterDevices<T>>::insert(device, attester_id);
+                writes = writes.saturating_add(1);
+            }
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// existing servicers start with all their attesters active
+    fn translate_servicers<T: Config>() -> Weight {
+        let mut translated = 0u64;
+
+        <Servicers<T>>::translate::<OldServicerInformation, _>(|_, old| {
+            translated = translated.saturating_add(1);
+
+            Some(ServicerInformation {
+                rep_positive: old.rep_positive,
+                rep_negative: old.rep_negative,
+                bindings: old.bindings,
+                inactive: UniqueSet::new(),
+            })
+        });
+
+        T::DbWeight::get().reads_writes(translated, translated)
+    }
+
+    /// existing bindings count as heartbeating in the upgrade window, instead
+    /// of all being marked inactive at the first check
+    fn seed_last_heartbeats<T: Config>() -> Weight {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let window = Pallet::<T>::heartbeat_window_of(now);
+        let mut reads = 1u64;
+        let mut writes = 0u64;
+
+        for servicer_info in <Servicers<T>>::iter_values() {
+            reads = reads.saturating_add(1);
+
+            for attester_id in servicer_info.bindings.keys() {
+                reads = reads.saturating_add(1);
+
+                if !<LastHeartbeats<T>>::contains_key(attester_id) {
+                    <LastHeartbeats<T>>::insert(attester_id, window);
+                    writes = writes.saturating_add(1);
+                }
+            }
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// existing mediators keep voting unscoped at the maximum quota, the old
+    /// set shares its storage prefix with the map so it is taken before
+    /// inserting
+    fn move_mediators<T: Config>() -> Weight {
+        let mediators = old::Mediators::<T>::take();
+        let moved = mediators.len() as u64;
+
+        for mediator in mediators {
+            <Mediators<T>>::insert(
+                mediator,
+                MediatorInformation {
+                    scope: MediatorScope::All,
+                    quota: T::MaxMediatorQuota::get(),
+                },
+            );
+        }
+
+        T::DbWeight::get().reads_writes(
+            moved.saturating_add(1),
+            moved.saturating_mul(2).saturating_add(1),
+        )
+    }
+}
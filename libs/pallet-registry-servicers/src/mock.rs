@@ -0,0 +1,225 @@
+//! Mock runtime for the Servicer Registry pallet tests
+
+use crate as nagara_registry_servicers;
+use frame_support::traits::{ConstU128, ConstU32, ConstU64};
+use sp_runtime::{AccountId32, BuildStorage};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const BIG_BROTHER: AccountId = AccountId32::new([1u8; 32]);
+pub const OTHER_BIG_BROTHER: AccountId = AccountId32::new([2u8; 32]);
+pub const SERVICER: AccountId = AccountId32::new([3u8; 32]);
+pub const OTHER_SERVICER: AccountId = AccountId32::new([4u8; 32]);
+pub const MEDIATOR: AccountId = AccountId32::new([5u8; 32]);
+pub const REGISTRAR: AccountId = AccountId32::new([6u8; 32]);
+pub const MEMBER: AccountId = AccountId32::new([7u8; 32]);
+
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+pub const BINDING_DEPOSIT: Balance = 10;
+pub const REGISTRATION_FEE: Balance = 10;
+pub const HEARTBEAT_WINDOW: BlockNumber = 10;
+pub const MAX_MISSED_HEARTBEATS: u32 = 2;
+pub const MEDIATOR_WINDOW: BlockNumber = 10;
+pub const PEER_ROTATION_COOLDOWN: BlockNumber = 10;
+pub const ADVERTISEMENT_TTL: BlockNumber = 100;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Identity: pallet_identity,
+        BigBrotherCouncil: nagara_council_bigbrothers,
+        ServicerRegistry: nagara_registry_servicers,
+    }
+);
+
+frame_support::parameter_types! {
+    pub BurnAddress: AccountId = AccountId32::new([255u8; 32]);
+    pub const RegistrationFeeBigBrotherDistribution: sp_runtime::Percent =
+        sp_runtime::Percent::from_percent(50);
+    pub static RevokedAttesters: Vec<nagara_registry_servicers::AttesterId> = vec![];
+}
+
+impl frame_system::Config for Test {
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type AccountId = AccountId;
+    type BaseCallFilter = frame_support::traits::Everything;
+    type Block = Block;
+    type BlockHashCount = ConstU64<250>;
+    type BlockLength = ();
+    type BlockWeights = ();
+    type DbWeight = ();
+    type Hash = sp_core::H256;
+    type Hashing = sp_runtime::traits::BlakeTwo256;
+    type Lookup = sp_runtime::traits::IdentityLookup<AccountId>;
+    type MaxConsumers = ConstU32<16>;
+    type Nonce = u64;
+    type OnKilledAccount = ();
+    type OnNewAccount = ();
+    type OnSetCode = ();
+    type PalletInfo = PalletInfo;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type SS58Prefix = ();
+    type SystemWeightInfo = ();
+    type Version = ();
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type Extrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+    type OverarchingCall = RuntimeCall;
+}
+
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type MaxHolds = ConstU32<4>;
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type WeightInfo = ();
+}
+
+impl pallet_identity::Config for Test {
+    type BasicDeposit = ConstU128<10>;
+    type Currency = Balances;
+    type FieldDeposit = ConstU128<1>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxAdditionalFields = ConstU32<2>;
+    type MaxRegistrars = ConstU32<4>;
+    type MaxSubAccounts = ConstU32<2>;
+    type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type Slashed = ();
+    type SubAccountDeposit = ConstU128<1>;
+    type WeightInfo = ();
+}
+
+impl nagara_council_bigbrothers::Config for Test {
+    type BurnAddress = BurnAddress;
+    type Currency = Balances;
+    type InitialMinimumTransactionFee = ConstU128<1>;
+    type InitialWeightToFeeDivider = ConstU64<1>;
+    type InitialWeightToFeeMultiplier = ConstU64<1>;
+    type MaxMembers = ConstU32<4>;
+    type RegistrationDepositAmount = ConstU128<100>;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl nagara_registry_servicers::Config for Test {
+    type AdvertisementTtl = ConstU64<ADVERTISEMENT_TTL>;
+    type BindingDepositAmount = ConstU128<BINDING_DEPOSIT>;
+    type Currency = Balances;
+    type HeartbeatPriority = ConstU64<1>;
+    type HeartbeatWindow = ConstU64<HEARTBEAT_WINDOW>;
+    type MaxAdvertisedEndpoints = ConstU32<4>;
+    type MaxAdvertisedProtocols = ConstU32<4>;
+    type MaxAdvertisementEntryLength = ConstU32<64>;
+    type MaxAttesterSupplyBatch = ConstU32<4>;
+    type MaxCooperativeMembers = ConstU32<2>;
+    type MaxHeartbeatChecksPerBlock = ConstU32<8>;
+    type MaxMediatorQuota = ConstU32<4>;
+    type MaxMediatorScope = ConstU32<4>;
+    type MaxMediators = ConstU32<4>;
+    type MaxMissedHeartbeats = ConstU32<MAX_MISSED_HEARTBEATS>;
+    type MediatorWindow = ConstU64<MEDIATOR_WINDOW>;
+    type OnAttesterRevoked = RecordRevokedAttesters;
+    type OnRegistrationFee = ();
+    type PeerRotationCooldown = ConstU64<PEER_ROTATION_COOLDOWN>;
+    type RegistrationFeeAmount = ConstU128<REGISTRATION_FEE>;
+    type RegistrationFeeBigBrotherDistribution = RegistrationFeeBigBrotherDistribution;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+/// Revocation hook keeping the revoked attesters in order
+pub struct RecordRevokedAttesters;
+
+impl nagara_registry_servicers::OnAttesterRevoked for RecordRevokedAttesters {
+    fn on_attester_revoked(attester_id: &nagara_registry_servicers::AttesterId) {
+        RevokedAttesters::mutate(|revoked| revoked.push(*attester_id));
+    }
+}
+
+pub fn attester_pair(seed: u8) -> sp_core::ed25519::Pair {
+    <sp_core::ed25519::Pair as sp_core::Pair>::from_seed(&[seed; 32])
+}
+
+pub fn attester(seed: u8) -> nagara_registry_servicers::AttesterId {
+    sp_core::Pair::public(&attester_pair(seed))
+}
+
+pub fn peer(seed: u8) -> nagara_registry_servicers::PeerId {
+    sp_core::ed25519::Public::from_raw([seed.saturating_add(100); 32])
+}
+
+pub fn supply_args(seed: u8) -> nagara_registry_servicers::RemoteAttestationDeviceSupplyArgs {
+    nagara_registry_servicers::RemoteAttestationDeviceSupplyArgs {
+        id: attester(seed),
+        guid: [seed; 16],
+        serial_number: seed as u32,
+    }
+}
+
+/// two big brothers in the council, the first one supplied two attesters,
+/// the servicer is binded to the first attester
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: [
+            BIG_BROTHER,
+            OTHER_BIG_BROTHER,
+            SERVICER,
+            OTHER_SERVICER,
+            MEDIATOR,
+            REGISTRAR,
+            MEMBER,
+        ]
+        .into_iter()
+        .map(|account| (account, INITIAL_BALANCE))
+        .collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    nagara_council_bigbrothers::GenesisConfig::<Test> {
+        elder: None,
+        big_brothers: vec![BIG_BROTHER, OTHER_BIG_BROTHER],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    nagara_registry_servicers::GenesisConfig::<Test> {
+        attesters: [1u8, 2]
+            .into_iter()
+            .map(|seed| (BIG_BROTHER, supply_args(seed)))
+            .collect(),
+        mediators: vec![],
+        servicers: vec![(SERVICER, vec![(attester(1), peer(1))])],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+
+    ext
+}
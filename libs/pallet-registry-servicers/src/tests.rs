@@ -0,0 +1,710 @@
+//! Unit tests for the Servicer Registry pallet
+
+use crate::{mock::ServicerRegistry, *};
+
+fn servicer() -> ServicerInformation {
+    ServicerInformation {
+        rep_positive: 0,
+        rep_negative: 0,
+        bindings: UniqueMap::new(),
+        inactive: UniqueSet::new(),
+    }
+}
+
+#[test]
+fn missed_challenge_raises_rep_negative() {
+    // a missed challenge only raises the negative counter
+    let mut servicer = servicer();
+    servicer.decrease_reputation();
+    servicer.decrease_reputation();
+
+    assert_eq!(servicer.rep_negative, 2);
+    assert_eq!(servicer.rep_positive, 0);
+    assert_eq!(servicer.get_total_reputation(), -2);
+}
+
+#[test]
+fn answered_challenge_raises_rep_positive() {
+    let mut servicer = servicer();
+    servicer.increase_reputation();
+    servicer.decrease_reputation();
+
+    assert_eq!(servicer.rep_positive, 1);
+    assert_eq!(servicer.rep_negative, 1);
+    assert_eq!(servicer.get_total_reputation(), 0);
+}
+
+#[test]
+fn reputation_counters_saturate() {
+    let mut servicer = servicer();
+    servicer.rep_negative = u32::MAX;
+    servicer.decrease_reputation();
+
+    assert_eq!(servicer.rep_negative, u32::MAX);
+}
+
+fn free_balance(who: &mock::AccountId) -> mock::Balance {
+    <mock::Balances as frame_support::traits::Currency<_>>::free_balance(who)
+}
+
+fn sign<P: codec::Encode>(seed: u8, context: [u8; 4], payload: &P) -> AttesterSignature {
+    let message = attester_signing_message(context, payload);
+
+    sp_core::Pair::sign(&mock::attester_pair(seed), &message)
+}
+
+/// give an account a legal name judged reasonable by a registrar
+fn verify_legality(who: &mock::AccountId) {
+    let info = pallet_identity::IdentityInfo {
+        additional: Default::default(),
+        display: pallet_identity::Data::None,
+        legal: pallet_identity::Data::Raw(b"Mediator Ltd".to_vec().try_into().unwrap()),
+        web: pallet_identity::Data::None,
+        riot: pallet_identity::Data::None,
+        email: pallet_identity::Data::None,
+        pgp_fingerprint: None,
+        image: pallet_identity::Data::None,
+        twitter: pallet_identity::Data::None,
+    };
+    let identity = <sp_runtime::traits::BlakeTwo256 as sp_runtime::traits::Hash>::hash_of(&info);
+    frame_support::assert_ok!(mock::Identity::add_registrar(
+        mock::RuntimeOrigin::root(),
+        mock::REGISTRAR,
+    ));
+    frame_support::assert_ok!(mock::Identity::set_identity(
+        mock::RuntimeOrigin::signed(who.clone()),
+        Box::new(info),
+    ));
+    frame_support::assert_ok!(mock::Identity::provide_judgement(
+        mock::RuntimeOrigin::signed(mock::REGISTRAR),
+        0,
+        who.clone(),
+        pallet_identity::Judgement::Reasonable,
+        identity,
+    ));
+}
+
+fn heartbeat(seed: u8, window: u32) -> (Heartbeat, AttesterSignature) {
+    let heartbeat = Heartbeat {
+        attester_id: mock::attester(seed),
+        window,
+    };
+    let signature = sign(seed, SIGNING_CONTEXT_HEARTBEAT, &heartbeat);
+
+    (heartbeat, signature)
+}
+
+fn rotation_signature(old_peer_id: PeerId, new_peer_id: PeerId, nonce: u32) -> AttesterSignature {
+    let payload = PeerRotationPayload {
+        servicer: mock::SERVICER,
+        attester_id: mock::attester(1),
+        old_peer_id,
+        new_peer_id,
+        nonce,
+    };
+
+    sign(1, SIGNING_CONTEXT_PEER_ROTATION, &payload)
+}
+
+#[test]
+fn peer_rotation_is_signed_and_rate_limited() {
+    mock::new_test_ext().execute_with(|| {
+        let signature = rotation_signature(mock::peer(1), mock::peer(2), 0);
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_peer_rotate(
+                mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+                mock::attester(1),
+                mock::peer(2),
+                signature.clone(),
+            ),
+            Error::<mock::Test>::AttesterNotBindedToCaller,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_peer_rotate(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::attester(1),
+                mock::peer(3),
+                signature.clone(),
+            ),
+            Error::<mock::Test>::InvalidAttesterSignature,
+        );
+        frame_support::assert_ok!(ServicerRegistry::sv_peer_rotate(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            mock::attester(1),
+            mock::peer(2),
+            signature.clone(),
+        ));
+
+        let servicer = ServicerRegistry::servicers(mock::SERVICER).unwrap();
+        assert_eq!(
+            servicer.get_peer_id(&mock::attester(1)),
+            Some(mock::peer(2))
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_peer_rotate(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::attester(1),
+                mock::peer(3),
+                rotation_signature(mock::peer(2), mock::peer(3), 1),
+            ),
+            Error::<mock::Test>::PeerRotationTooFrequent,
+        );
+
+        // the nonce prevents replaying a rotation back to an older peer id
+        mock::System::set_block_number(1 + mock::PEER_ROTATION_COOLDOWN);
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_peer_rotate(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::attester(1),
+                mock::peer(1),
+                rotation_signature(mock::peer(2), mock::peer(1), 0),
+            ),
+            Error::<mock::Test>::InvalidAttesterSignature,
+        );
+        frame_support::assert_ok!(ServicerRegistry::sv_peer_rotate(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            mock::attester(1),
+            mock::peer(1),
+            rotation_signature(mock::peer(2), mock::peer(1), 1),
+        ));
+        assert_eq!(
+            ServicerRegistry::peer_rotations(mock::attester(1))
+                .unwrap()
+                .nonce,
+            2
+        );
+    });
+}
+
+#[test]
+fn attester_batch_supply_is_all_or_nothing() {
+    mock::new_test_ext().execute_with(|| {
+        let batch = |seeds: &[u8]| -> frame_support::BoundedVec<
+            _,
+            <mock::Test as Config>::MaxAttesterSupplyBatch,
+        > {
+            seeds
+                .iter()
+                .map(|seed| mock::supply_args(*seed))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap()
+        };
+
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_supply_batch(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                batch(&[3]),
+            ),
+            ngr_bbcm::Error::<mock::Test>::CouncilMemberOnly,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_supply_batch(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                batch(&[]),
+            ),
+            Error::<mock::Test>::EmptySupplyBatch,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_supply_batch(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                batch(&[3, 3]),
+            ),
+            Error::<mock::Test>::AttesterAlreadySupplied,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_supply_batch(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                batch(&[3, 1]),
+            ),
+            Error::<mock::Test>::AttesterAlreadySupplied,
+        );
+
+        let mut same_device = mock::supply_args(4);
+        same_device.guid = [3; 16];
+        same_device.serial_number = 3;
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_supply_batch(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                vec![mock::supply_args(3), same_device].try_into().unwrap(),
+            ),
+            Error::<mock::Test>::AttesterDeviceAlreadySupplied,
+        );
+
+        frame_support::assert_ok!(ServicerRegistry::bb_attester_supply_batch(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            batch(&[3, 4]),
+        ));
+        mock::System::assert_last_event(
+            Event::<mock::Test>::BigBrotherAttestersBatchSupplied {
+                bb: mock::BIG_BROTHER,
+                count: 2,
+            }
+            .into(),
+        );
+        for seed in [3, 4] {
+            let attester = ServicerRegistry::attesters(mock::attester(seed)).unwrap();
+            assert_eq!(attester.big_brother, mock::BIG_BROTHER);
+            assert!(!attester.is_binded());
+        }
+    });
+}
+
+#[test]
+fn revoked_attester_is_reported_and_never_trusted_again() {
+    mock::new_test_ext().execute_with(|| {
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_revoke(
+                mock::RuntimeOrigin::signed(mock::OTHER_BIG_BROTHER),
+                mock::attester(2),
+            ),
+            Error::<mock::Test>::RestrictedCall,
+        );
+        frame_support::assert_ok!(ServicerRegistry::bb_attester_revoke(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::attester(2),
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_revoke(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::attester(2),
+            ),
+            Error::<mock::Test>::AttesterRevoked,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_attester_bind(
+                mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+                mock::peer(2),
+                mock::attester(2),
+            ),
+            Error::<mock::Test>::AttesterRevoked,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_recall(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::attester(2),
+            ),
+            Error::<mock::Test>::AttesterRevoked,
+        );
+
+        // a binded attester is revoked by sudo, its servicer loses it
+        frame_support::assert_ok!(ServicerRegistry::se_attester_revoke(
+            mock::RuntimeOrigin::root(),
+            mock::attester(1),
+        ));
+        mock::System::assert_last_event(
+            Event::<mock::Test>::AttesterRevoked {
+                id: mock::attester(1),
+                by: None,
+            }
+            .into(),
+        );
+        assert!(ServicerRegistry::is_attester_revoked(&mock::attester(1)));
+        assert!(!ServicerRegistry::is_attester_active(&mock::attester(1)));
+        assert_eq!(
+            mock::RevokedAttesters::get(),
+            vec![mock::attester(2), mock::attester(1)]
+        );
+
+        let (heartbeat, signature) = heartbeat(1, 0);
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_heartbeat(mock::RuntimeOrigin::none(), heartbeat, signature),
+            Error::<mock::Test>::AttesterRevoked,
+        );
+    });
+}
+
+#[test]
+fn missed_heartbeats_inactivate_the_attester_until_the_next_one() {
+    mock::new_test_ext().execute_with(|| {
+        use frame_support::traits::Hooks;
+
+        let late = mock::HEARTBEAT_WINDOW * (mock::MAX_MISSED_HEARTBEATS as u64 + 1);
+        let window = (late / mock::HEARTBEAT_WINDOW) as u32;
+        mock::System::set_block_number(late - 1);
+        ServicerRegistry::on_idle(late - 1, frame_support::weights::Weight::MAX);
+        assert!(ServicerRegistry::is_attester_active(&mock::attester(1)));
+
+        mock::System::set_block_number(late);
+        ServicerRegistry::on_idle(late, frame_support::weights::Weight::MAX);
+        mock::System::assert_last_event(
+            Event::<mock::Test>::AttesterInactivated {
+                who: mock::SERVICER,
+                which: mock::attester(1),
+            }
+            .into(),
+        );
+        assert!(!ServicerRegistry::is_attester_active(&mock::attester(1)));
+
+        let (stale, signature) = heartbeat(1, window - 1);
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_heartbeat(mock::RuntimeOrigin::none(), stale, signature),
+            Error::<mock::Test>::HeartbeatWindowMismatch,
+        );
+        let (forged, _) = heartbeat(1, window);
+        let (_, signature) = heartbeat(2, window);
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_heartbeat(mock::RuntimeOrigin::none(), forged, signature),
+            Error::<mock::Test>::InvalidAttesterSignature,
+        );
+
+        let (beat, signature) = heartbeat(1, window);
+        frame_support::assert_ok!(ServicerRegistry::sv_heartbeat(
+            mock::RuntimeOrigin::none(),
+            beat.clone(),
+            signature.clone(),
+        ));
+        mock::System::assert_last_event(
+            Event::<mock::Test>::AttesterReactivated {
+                who: mock::SERVICER,
+                which: mock::attester(1),
+            }
+            .into(),
+        );
+        assert!(ServicerRegistry::is_attester_active(&mock::attester(1)));
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_heartbeat(mock::RuntimeOrigin::none(), beat, signature),
+            Error::<mock::Test>::HeartbeatAlreadyReceived,
+        );
+    });
+}
+
+#[test]
+fn mediator_quota_is_spent_once_per_attester_per_window() {
+    mock::new_test_ext().execute_with(|| {
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_mediator_add(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::MEDIATOR,
+                MediatorScope::All,
+                1,
+            ),
+            ngr_bbcm::Error::<mock::Test>::AccountHasNoLegalName,
+        );
+
+        verify_legality(&mock::MEDIATOR);
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_mediator_add(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::MEDIATOR,
+                MediatorScope::All,
+                5,
+            ),
+            Error::<mock::Test>::MediatorQuotaTooHigh,
+        );
+        frame_support::assert_ok!(ServicerRegistry::bb_mediator_add(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::MEDIATOR,
+            MediatorScope::All,
+            1,
+        ));
+        frame_support::assert_ok!(ServicerRegistry::sv_attester_bind(
+            mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+            mock::peer(2),
+            mock::attester(2),
+        ));
+
+        frame_support::assert_ok!(ServicerRegistry::md_rep_increase(
+            mock::RuntimeOrigin::signed(mock::MEDIATOR),
+            mock::attester(1),
+        ));
+        assert_eq!(
+            ServicerRegistry::servicers(mock::SERVICER)
+                .unwrap()
+                .rep_positive,
+            1
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::md_rep_decrease(
+                mock::RuntimeOrigin::signed(mock::MEDIATOR),
+                mock::attester(1),
+            ),
+            Error::<mock::Test>::MediatorAlreadyVoted,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::md_rep_decrease(
+                mock::RuntimeOrigin::signed(mock::MEDIATOR),
+                mock::attester(2),
+            ),
+            Error::<mock::Test>::MediatorQuotaExceeded,
+        );
+
+        // the quota & votes are reset in the next window
+        mock::System::set_block_number(mock::MEDIATOR_WINDOW);
+        frame_support::assert_ok!(ServicerRegistry::md_rep_decrease(
+            mock::RuntimeOrigin::signed(mock::MEDIATOR),
+            mock::attester(1),
+        ));
+        assert_eq!(
+            ServicerRegistry::servicers(mock::SERVICER)
+                .unwrap()
+                .rep_negative,
+            1
+        );
+
+        // a scoped mediator only votes on its servicers
+        let scope = vec![mock::OTHER_SERVICER]
+            .into_iter()
+            .collect::<sp_std::collections::btree_set::BTreeSet<_>>()
+            .try_into()
+            .unwrap();
+        frame_support::assert_ok!(ServicerRegistry::bb_mediator_update(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::MEDIATOR,
+            MediatorScope::Servicers(scope),
+            2,
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::md_rep_increase(
+                mock::RuntimeOrigin::signed(mock::MEDIATOR),
+                mock::attester(1),
+            ),
+            Error::<mock::Test>::MediatorOutOfScope,
+        );
+        frame_support::assert_ok!(ServicerRegistry::md_rep_increase(
+            mock::RuntimeOrigin::signed(mock::MEDIATOR),
+            mock::attester(2),
+        ));
+    });
+}
+
+#[test]
+fn attester_transfer_is_offered_then_accepted() {
+    mock::new_test_ext().execute_with(|| {
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_transfer_offer(
+                mock::RuntimeOrigin::signed(mock::OTHER_BIG_BROTHER),
+                mock::attester(1),
+                mock::BIG_BROTHER,
+            ),
+            Error::<mock::Test>::RestrictedCall,
+        );
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_transfer_offer(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::attester(1),
+                mock::SERVICER,
+            ),
+            Error::<mock::Test>::AttesterTransferReceiverNotBigBrother,
+        );
+        frame_support::assert_ok!(ServicerRegistry::bb_attester_transfer_offer(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::attester(1),
+            mock::OTHER_BIG_BROTHER,
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::bb_attester_transfer_accept(
+                mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+                mock::attester(1),
+            ),
+            Error::<mock::Test>::AttesterTransferNotFound,
+        );
+        frame_support::assert_ok!(ServicerRegistry::bb_attester_transfer_accept(
+            mock::RuntimeOrigin::signed(mock::OTHER_BIG_BROTHER),
+            mock::attester(1),
+        ));
+
+        let attester = ServicerRegistry::attesters(mock::attester(1)).unwrap();
+        assert_eq!(attester.big_brother, mock::OTHER_BIG_BROTHER);
+        assert_eq!(attester.binder, Some(mock::SERVICER)); // binding is untouched
+        assert!(ServicerRegistry::attester_transfers(mock::attester(1)).is_none());
+
+        // forcing is only for attesters of big brothers who left the council
+        frame_support::assert_noop!(
+            ServicerRegistry::se_attester_transfer_force(
+                mock::RuntimeOrigin::root(),
+                mock::attester(2),
+                mock::OTHER_BIG_BROTHER,
+            ),
+            Error::<mock::Test>::AttesterSupplierStillBigBrother,
+        );
+    });
+}
+
+#[test]
+fn cooperative_members_share_the_servicer_revenue() {
+    mock::new_test_ext().execute_with(|| {
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_cooperative_create(mock::RuntimeOrigin::signed(mock::MEMBER)),
+            Error::<mock::Test>::ServicerNotFound,
+        );
+        assert_eq!(
+            ServicerRegistry::revenue_shares(&mock::SERVICER),
+            vec![(mock::SERVICER, 1)]
+        );
+        frame_support::assert_ok!(ServicerRegistry::sv_cooperative_create(
+            mock::RuntimeOrigin::signed(mock::SERVICER)
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::sv_cooperative_create(mock::RuntimeOrigin::signed(mock::SERVICER)),
+            Error::<mock::Test>::CooperativeAlreadyExist,
+        );
+
+        frame_support::assert_noop!(
+            ServicerRegistry::co_member_set(
+                mock::RuntimeOrigin::signed(mock::MEMBER),
+                mock::SERVICER,
+                mock::MEMBER,
+                3,
+            ),
+            Error::<mock::Test>::NotCooperativeAdmin,
+        );
+        frame_support::assert_ok!(ServicerRegistry::co_member_set(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            mock::SERVICER,
+            mock::MEMBER,
+            3,
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::co_member_set(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::SERVICER,
+                mock::MEDIATOR,
+                1,
+            ),
+            Error::<mock::Test>::TooManyCooperativeMembers,
+        );
+        frame_support::assert_ok!(ServicerRegistry::co_member_set(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            mock::SERVICER,
+            mock::SERVICER,
+            0,
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::co_member_set(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::SERVICER,
+                mock::MEMBER,
+                0,
+            ),
+            Error::<mock::Test>::CooperativeWithoutShares,
+        );
+        assert_eq!(
+            ServicerRegistry::revenue_shares(&mock::SERVICER),
+            vec![(mock::MEMBER, 3)]
+        );
+
+        frame_support::assert_ok!(ServicerRegistry::co_admin_set(
+            mock::RuntimeOrigin::signed(mock::SERVICER),
+            mock::SERVICER,
+            mock::MEMBER,
+        ));
+        frame_support::assert_noop!(
+            ServicerRegistry::co_dissolve(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::SERVICER,
+            ),
+            Error::<mock::Test>::NotCooperativeAdmin,
+        );
+        frame_support::assert_ok!(ServicerRegistry::co_dissolve(
+            mock::RuntimeOrigin::signed(mock::MEMBER),
+            mock::SERVICER,
+        ));
+        assert_eq!(
+            ServicerRegistry::revenue_shares(&mock::SERVICER),
+            vec![(mock::SERVICER, 1)]
+        );
+    });
+}
+
+#[test]
+fn registration_fee_is_split_with_the_supplying_big_brother() {
+    mock::new_test_ext().execute_with(|| {
+        let issuance = <mock::Balances as frame_support::traits::Currency<_>>::total_issuance();
+        frame_support::assert_ok!(ServicerRegistry::bb_attester_supply(
+            mock::RuntimeOrigin::signed(mock::BIG_BROTHER),
+            mock::supply_args(3),
+        ));
+
+        frame_support::assert_ok!(ServicerRegistry::sv_attester_bind(
+            mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+            mock::peer(2),
+            mock::attester(2),
+        ));
+        let big_brother_part = mock::REGISTRATION_FEE / 2;
+        assert_eq!(
+            free_balance(&mock::OTHER_SERVICER),
+            mock::INITIAL_BALANCE - mock::REGISTRATION_FEE - mock::BINDING_DEPOSIT
+        );
+        assert_eq!(
+            free_balance(&mock::BIG_BROTHER),
+            mock::INITIAL_BALANCE + big_brother_part
+        );
+        // the remainder goes to `OnRegistrationFee`, dropped here
+        assert_eq!(
+            <mock::Balances as frame_support::traits::Currency<_>>::total_issuance(),
+            issuance - (mock::REGISTRATION_FEE - big_brother_part)
+        );
+
+        // the fee is paid once per servicer, the deposit once per attester
+        frame_support::assert_ok!(ServicerRegistry::sv_attester_bind(
+            mock::RuntimeOrigin::signed(mock::OTHER_SERVICER),
+            mock::peer(3),
+            mock::attester(3),
+        ));
+        assert_eq!(
+            free_balance(&mock::OTHER_SERVICER),
+            mock::INITIAL_BALANCE - mock::REGISTRATION_FEE - 2 * mock::BINDING_DEPOSIT
+        );
+        assert_eq!(
+            free_balance(&mock::BIG_BROTHER),
+            mock::INITIAL_BALANCE + big_brother_part
+        );
+    });
+}
+
+#[test]
+fn pda_files_see_active_unrevoked_attesters_and_fresh_advertisements() {
+    mock::new_test_ext().execute_with(|| {
+        assert!(ServicerRegistry::is_attester_active(&mock::attester(1)));
+        assert!(!ServicerRegistry::is_attester_active(&mock::attester(2))); // unbinded
+        assert!(!ServicerRegistry::is_attester_revoked(&mock::attester(1)));
+
+        let entry =
+            |raw: &[u8]| -> AdvertisementEntryOf<mock::Test> { raw.to_vec().try_into().unwrap() };
+        let args = |version: u32, region: RegionCode| {
+            ServiceAdvertisementArgs::<mock::Test> {
+                version,
+                endpoints: vec![entry(b"/dns4/servicer.example/tcp/30333")]
+                    .try_into()
+                    .unwrap(),
+                protocols: vec![entry(b"/nagara/pda/1")].try_into().unwrap(),
+                region,
+                capacity: 1_024,
+            }
+        };
+        let advertise = |args: ServiceAdvertisementArgs<mock::Test>| {
+            let signature = sign(
+                1,
+                SIGNING_CONTEXT_ADVERTISEMENT,
+                &(&mock::SERVICER, &mock::attester(1), &args),
+            );
+            ServicerRegistry::sv_advertise(
+                mock::RuntimeOrigin::signed(mock::SERVICER),
+                mock::attester(1),
+                args,
+                signature,
+            )
+        };
+
+        frame_support::assert_noop!(
+            advertise(args(1, *b"id")),
+            Error::<mock::Test>::InvalidRegionCode
+        );
+        frame_support::assert_ok!(advertise(args(1, *b"ID")));
+        frame_support::assert_noop!(
+            advertise(args(1, *b"ID")),
+            Error::<mock::Test>::AdvertisementVersionTooLow,
+        );
+        assert_eq!(
+            ServicerRegistry::active_advertisement(&mock::attester(1))
+                .unwrap()
+                .info
+                .capacity,
+            1_024
+        );
+
+        mock::System::set_block_number(1 + mock::ADVERTISEMENT_TTL);
+        assert!(ServicerRegistry::active_advertisement(&mock::attester(1)).is_none());
+    });
+}
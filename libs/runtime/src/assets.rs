@@ -2,7 +2,7 @@ impl pallet_assets::Config for crate::Runtime {
     type ApprovalDeposit = crate::ApprovalDeposit;
     type AssetAccountDeposit = crate::ConstU128<{ crate::constants::TOKEN }>;
     type AssetDeposit = crate::AssetDeposit;
-    type AssetId = u32;
+    type AssetId = crate::AssetId;
     type AssetIdParameter = codec::Compact<u32>;
     type Balance = crate::Balance;
     type CallbackHandle = ();
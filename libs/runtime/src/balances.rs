@@ -13,3 +13,41 @@ impl pallet_balances::Config for crate::Runtime {
     type RuntimeHoldReason = crate::RuntimeHoldReason;
     type WeightInfo = pallet_balances::weights::SubstrateWeight<crate::Runtime>;
 }
+
+/// Credits unbalanced amounts (e.g. fee remainders) to the chain treasury
+/// account
+pub struct ToChainTreasury;
+
+impl frame_support::traits::OnUnbalanced<pallet_balances::NegativeImbalance<crate::Runtime>>
+    for ToChainTreasury
+{
+    fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<crate::Runtime>) {
+        <crate::Balances as frame_support::traits::Currency<crate::AccountId>>::resolve_creating(
+            &chain_treasury(),
+            amount,
+        );
+    }
+}
+
+impl
+    frame_support::traits::OnUnbalanced<
+        frame_support::traits::fungibles::Credit<crate::AccountId, crate::Assets>,
+    > for ToChainTreasury
+{
+    fn on_nonzero_unbalanced(
+        amount: frame_support::traits::fungibles::Credit<crate::AccountId, crate::Assets>,
+    ) {
+        // burned if the treasury can't hold the asset (below its minimum balance)
+        let _ = <crate::Assets as frame_support::traits::fungibles::Balanced<
+            crate::AccountId,
+        >>::resolve(&chain_treasury(), amount);
+    }
+}
+
+fn chain_treasury() -> crate::AccountId {
+    let treasury_pallet_id = <crate::ChainTreasuryPalletId as frame_support::traits::Get<_>>::get();
+
+    <frame_support::PalletId as sp_runtime::traits::AccountIdConversion<
+        crate::AccountId,
+    >>::into_account_truncating(&treasury_pallet_id)
+}
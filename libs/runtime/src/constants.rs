@@ -1,19 +1,49 @@
 // region: consensus
 
+pub const ADVERTISEMENT_TTL: crate::BlockNumber = 7 * DAYS;
 pub const ALLOW_MULTIPLE_BLOCKS_PER_SLOT: bool = false;
 pub const ATTESTER_BINDING_HOLD: crate::Balance = 1 * TOKEN;
 pub const AUTHORITY_SESSION_OFFSET: u32 = 0;
 pub const AUTHORITY_SESSION_PERIOD: u32 = 2 * MINUTES;
+pub const CHALLENGE_RESPONSE_PERIOD: crate::BlockNumber = 1 * HOURS;
 pub const CONSENSUS_SLOT_DURATION: u64 = BLOCKTIME_MS;
+pub const FILE_GRACE_PERIOD: crate::BlockNumber = 7 * DAYS;
+pub const HEARTBEAT_PRIORITY: u64 = u64::MAX / 2;
+pub const HEARTBEAT_WINDOW: crate::BlockNumber = 1 * HOURS;
 pub const INITIAL_MINIMUM_TRANSACTION_FEE: crate::Balance = get_fee(1, 1024);
 pub const INITIAL_WEIGHT_TO_FEE_DIVIDER: u64 = 16 * 1024;
 pub const INITIAL_WEIGHT_TO_FEE_MULTIPLIER: u64 = 1;
+pub const LICENSE_DURATION: crate::BlockNumber = 30 * DAYS;
+pub const MAX_ACCESS_LIST_ENTRIES: u32 = 1_024;
+pub const MAX_ADVERTISED_ENDPOINTS: u32 = 8;
+pub const MAX_ADVERTISED_PROTOCOLS: u32 = 8;
+pub const MAX_ADVERTISEMENT_ENTRY_LENGTH: u32 = 256;
+pub const MAX_ATTESTER_SUPPLY_BATCH: u32 = 512;
+pub const MAX_AUCTION_DURATION: crate::BlockNumber = 30 * DAYS;
 pub const MAX_AUTHORITIES: u8 = 16;
+pub const MAX_CHUNK_SIZE: u32 = 256 * 1024;
+pub const MAX_COOPERATIVE_MEMBERS: u32 = 64;
+pub const MAX_FILES_PER_ACCOUNT: u32 = 4_096;
+pub const MAX_FILE_ENTRY_REMOVALS_PER_BLOCK: u32 = 1_024;
+pub const MAX_FILE_INDEX_BACKFILLS_PER_BLOCK: u32 = 256;
+pub const MAX_FILE_OFFERS: u32 = 64;
+pub const MAX_FILE_REPLICAS: u32 = 8;
+pub const MAX_HEARTBEAT_CHECKS_PER_BLOCK: u32 = 128;
 pub const MAX_MEDIATORS: u32 = 32;
+pub const MAX_MEDIATOR_QUOTA: u32 = 256;
+pub const MAX_MEDIATOR_SCOPE: u32 = 64;
+pub const MAX_MERKLE_PROOF_DEPTH: u32 = 32;
+pub const MAX_MISSED_HEARTBEATS: u32 = 3;
 pub const MAX_NOMINATORS: u32 = 0;
+pub const MAX_REPLICA_REVOCATIONS_PER_BLOCK: u32 = 256;
+pub const MAX_ROYALTY_BENEFICIARIES: u32 = 16;
 pub const MAX_SET_ID_SESSION_ENTRIES: u64 = 0;
+pub const MAX_STORAGE_FEE_CHARGES_PER_BLOCK: u32 = 128;
+pub const MEDIATOR_QUOTA_WINDOW: crate::BlockNumber = 1 * HOURS;
 pub const MIN_AUTHORITIES: u8 = 1;
 pub const NORMAL_DISPATCH_RATIO: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(90);
+pub const PEER_ROTATION_COOLDOWN: crate::BlockNumber = 1 * DAYS;
+pub const PRICING_NOTICE_PERIOD: crate::BlockNumber = 3 * DAYS;
 pub const PROPOSAL_APPROVAL_PERCENT: sp_runtime::Percent = sp_runtime::Percent::from_percent(75);
 pub const SERVICER_REGISTRATION_FEE: crate::Balance = 4 * TOKEN;
 pub const STORAGE_PERIOD: crate::BlockNumber = 1 * DAYS;
@@ -44,6 +74,7 @@ pub const CHAIN_BURN_ADDRESS: sp_core::crypto::AccountId32 = sp_core::crypto::Ac
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
 ]);
+pub const CHAIN_TREASURY_PALLET_ID: frame_support::PalletId = frame_support::PalletId(*b"ngr/trsy");
 pub const DEPOSIT_PER_BYTE: crate::Balance = get_fee(0, 1);
 pub const DEPOSIT_PER_ITEM: crate::Balance = get_fee(1, 0);
 pub const ERC20_APPROVAL_DEPOSIT: crate::Balance = 32 * TOKEN;
@@ -28,6 +28,7 @@ pub mod validator_set;
 
 pub type AccountId = <<crate::Signature as sp_runtime::traits::Verify>::Signer as sp_runtime::traits::IdentifyAccount>::AccountId;
 pub type Address = sp_runtime::MultiAddress<crate::AccountId, ()>;
+pub type AssetId = u32;
 pub type AuraId = sp_consensus_aura::sr25519::AuthorityId;
 pub type Balance = u128;
 pub type Block = sp_runtime::generic::Block<crate::Header, crate::UncheckedExtrinsic>;
@@ -43,6 +44,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     crate::Runtime,
     crate::AllPalletsWithSystem,
+    crate::Migrations,
 >;
 pub type EventRecord = frame_system::EventRecord<
     <crate::Runtime as frame_system::Config>::RuntimeEvent,
@@ -51,6 +53,11 @@ pub type EventRecord = frame_system::EventRecord<
 pub type GrandpaId = sp_consensus_grandpa::AuthorityId;
 pub type Hash = sp_core::H256;
 pub type Header = sp_runtime::generic::Header<BlockNumber, sp_runtime::traits::BlakeTwo256>;
+pub type Migrations = (
+    nagara_registry_servicers::migrations::v1::MigrateToV1<crate::Runtime>,
+    nagara_pda_files::migrations::v1::MigrateFileLayout<crate::Runtime>,
+    nagara_pda_files::migrations::v2::BackfillFileIndexes<crate::Runtime>,
+);
 pub type Nonce = u32;
 pub type Signature = sp_runtime::MultiSignature;
 pub type SignedExtra = (
@@ -99,9 +106,9 @@ pub const VERSION: sp_version::RuntimeVersion = sp_version::RuntimeVersion {
     apis: crate::RUNTIME_API_VERSIONS,
     authoring_version: 3,
     impl_version: 6,
-    spec_version: 157,
+    spec_version: 158,
     state_version: 8,
-    transaction_version: 8,
+    transaction_version: 9,
 };
 
 #[cfg(feature = "std")]
@@ -118,11 +125,14 @@ parameter_types! {
     pub const BigBrotherDownloadFeeDistribution:  Percent = Percent::from_percent(20);
     pub const BlockHashCount: crate::BlockNumber = 2400;
     pub const ChainBurnAddress: sp_core::crypto::AccountId32 = constants::CHAIN_BURN_ADDRESS;
+    pub const ChainTreasuryPalletId: frame_support::PalletId = constants::CHAIN_TREASURY_PALLET_ID;
     pub const DepositBase: Balance = constants::MULTISIG_DEPOSIT_BASE;
     pub const DepositFactor: Balance = constants::MULTISIG_DEPOSIT_FACTOR;
+    pub const EarlyTerminationFee: Percent = Percent::from_percent(10);
     pub const MaxAdditionalFields: u32 = constants::IDENTITY_MAX_ADDITIONAL_FIELDS;
     pub const MetadataDepositBase: Balance = constants::ERC20_METADATA_DEPOSIT_PER_ITEM;
     pub const MetadataDepositPerByte: Balance = constants::ERC20_METADATA_DEPOSIT_PER_BYTE;
+    pub const RegistrationFeeBigBrotherDistribution: Percent = Percent::from_percent(50);
     pub const RoyaltyFeeDistribution: Percent = Percent::from_percent(5);
     pub const ServicerUploadFeeDistribution: Percent = Percent::from_percent(40);
     pub const SS58Prefix: u16 = ss58_registry::Ss58AddressFormatRegistry::NagaraAccount as u16;
@@ -416,6 +426,40 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl nagara_pda_files::runtime_api::PdaFilesApi<crate::Block, crate::AccountId, crate::AssetId, crate::Balance, crate::BlockNumber> for crate::Runtime {
+        fn download_access(file: crate::AccountId, who: crate::AccountId) -> Option<nagara_pda_files::DownloadAccess<crate::Balance>> {
+            crate::PDAFiles::download_access(&file, &who)
+        }
+
+        fn license(file: crate::AccountId, who: crate::AccountId) -> Option<nagara_pda_files::License<crate::BlockNumber>> {
+            crate::PDAFiles::licenses(file, who)
+        }
+
+        fn file(file: crate::AccountId) -> Option<nagara_pda_files::FileSummaryOf<crate::Runtime>> {
+            crate::PDAFiles::file_summary(&file)
+        }
+
+        fn file_account_of(hash: nagara_pda_files::FileHash, uploader: crate::AccountId) -> crate::AccountId {
+            crate::PDAFiles::file_account_of(&hash, &uploader)
+        }
+
+        fn file_by_hash(hash: nagara_pda_files::FileHash) -> Option<(crate::AccountId, nagara_pda_files::FileSummaryOf<crate::Runtime>)> {
+            crate::PDAFiles::file_by_hash(&hash)
+        }
+
+        fn files_of_owner(owner: crate::AccountId) -> Vec<crate::AccountId> {
+            crate::PDAFiles::files_of_owner(&owner)
+        }
+
+        fn quote(size: u64, pricing_asset: Option<crate::AssetId>) -> Option<nagara_pda_files::FeeQuote<crate::Balance>> {
+            crate::PDAFiles::quote(size, pricing_asset)
+        }
+
+        fn file_quote(file: crate::AccountId) -> Option<nagara_pda_files::FileQuote<crate::Balance>> {
+            crate::PDAFiles::file_quote(&file)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<crate::Block> for crate::Runtime {
         fn benchmark_metadata(extra: bool) -> (
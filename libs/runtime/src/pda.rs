@@ -1,7 +1,32 @@
 impl nagara_pda_files::Config for crate::Runtime {
+    type Assets = crate::Assets;
     type BigBrotherDownloadFeeDistribution = crate::BigBrotherDownloadFeeDistribution;
+    type ChallengeResponsePeriod = crate::ConstU32<{ crate::constants::CHALLENGE_RESPONSE_PERIOD }>;
     type Currency = crate::Balances;
+    type EarlyTerminationFee = crate::EarlyTerminationFee;
+    type GracePeriod = crate::ConstU32<{ crate::constants::FILE_GRACE_PERIOD }>;
+    type LicenseDuration = crate::ConstU32<{ crate::constants::LICENSE_DURATION }>;
+    type MaxAccessListEntries = crate::ConstU32<{ crate::constants::MAX_ACCESS_LIST_ENTRIES }>;
+    type MaxAuctionDuration = crate::ConstU32<{ crate::constants::MAX_AUCTION_DURATION }>;
+    type MaxChunkSize = crate::ConstU32<{ crate::constants::MAX_CHUNK_SIZE }>;
+    type MaxFileEntryRemovalsPerBlock =
+        crate::ConstU32<{ crate::constants::MAX_FILE_ENTRY_REMOVALS_PER_BLOCK }>;
+    type MaxFileIndexBackfillsPerBlock =
+        crate::ConstU32<{ crate::constants::MAX_FILE_INDEX_BACKFILLS_PER_BLOCK }>;
+    type MaxFilesPerAccount = crate::ConstU32<{ crate::constants::MAX_FILES_PER_ACCOUNT }>;
+    type MaxOffersPerFile = crate::ConstU32<{ crate::constants::MAX_FILE_OFFERS }>;
+    type MaxProofDepth = crate::ConstU32<{ crate::constants::MAX_MERKLE_PROOF_DEPTH }>;
+    type MaxReplicaRevocationsPerBlock =
+        crate::ConstU32<{ crate::constants::MAX_REPLICA_REVOCATIONS_PER_BLOCK }>;
+    type MaxReplicas = crate::ConstU32<{ crate::constants::MAX_FILE_REPLICAS }>;
+    type MaxRoyaltyBeneficiaries = crate::ConstU32<{ crate::constants::MAX_ROYALTY_BENEFICIARIES }>;
+    type MaxStorageFeeChargesPerBlock =
+        crate::ConstU32<{ crate::constants::MAX_STORAGE_FEE_CHARGES_PER_BLOCK }>;
     type MinDownloadFeePerByte = MinDownloadFeePerByte<crate::Balance>;
+    type OnAssetFeeRemainder = crate::balances::ToChainTreasury;
+    type OnFeeRemainder = crate::balances::ToChainTreasury;
+    type PricingNoticePeriod = crate::ConstU32<{ crate::constants::PRICING_NOTICE_PERIOD }>;
+    type Randomness = crate::RandomnessCollectiveFlip;
     type RoyaltyFeeDistribution = crate::RoyaltyFeeDistribution;
     type RuntimeEvent = crate::RuntimeEvent;
     type ServicerUploadFeeDistribution = crate::ServicerUploadFeeDistribution;
@@ -1,8 +1,27 @@
 impl nagara_registry_servicers::Config for crate::Runtime {
+    type AdvertisementTtl = crate::ConstU32<{ crate::constants::ADVERTISEMENT_TTL }>;
     type BindingDepositAmount = crate::ConstU128<{ crate::constants::ATTESTER_BINDING_HOLD }>;
     type Currency = crate::Balances;
+    type HeartbeatPriority = crate::ConstU64<{ crate::constants::HEARTBEAT_PRIORITY }>;
+    type HeartbeatWindow = crate::ConstU32<{ crate::constants::HEARTBEAT_WINDOW }>;
+    type MaxAdvertisedEndpoints = crate::ConstU32<{ crate::constants::MAX_ADVERTISED_ENDPOINTS }>;
+    type MaxAdvertisedProtocols = crate::ConstU32<{ crate::constants::MAX_ADVERTISED_PROTOCOLS }>;
+    type MaxAdvertisementEntryLength =
+        crate::ConstU32<{ crate::constants::MAX_ADVERTISEMENT_ENTRY_LENGTH }>;
+    type MaxAttesterSupplyBatch = crate::ConstU32<{ crate::constants::MAX_ATTESTER_SUPPLY_BATCH }>;
+    type MaxCooperativeMembers = crate::ConstU32<{ crate::constants::MAX_COOPERATIVE_MEMBERS }>;
+    type MaxHeartbeatChecksPerBlock =
+        crate::ConstU32<{ crate::constants::MAX_HEARTBEAT_CHECKS_PER_BLOCK }>;
+    type MaxMediatorQuota = crate::ConstU32<{ crate::constants::MAX_MEDIATOR_QUOTA }>;
+    type MaxMediatorScope = crate::ConstU32<{ crate::constants::MAX_MEDIATOR_SCOPE }>;
     type MaxMediators = crate::ConstU32<{ crate::constants::MAX_MEDIATORS }>;
+    type MaxMissedHeartbeats = crate::ConstU32<{ crate::constants::MAX_MISSED_HEARTBEATS }>;
+    type MediatorWindow = crate::ConstU32<{ crate::constants::MEDIATOR_QUOTA_WINDOW }>;
+    type OnAttesterRevoked = crate::PDAFiles;
+    type OnRegistrationFee = crate::balances::ToChainTreasury;
+    type PeerRotationCooldown = crate::ConstU32<{ crate::constants::PEER_ROTATION_COOLDOWN }>;
     type RegistrationFeeAmount = crate::ConstU128<{ crate::constants::SERVICER_REGISTRATION_FEE }>;
+    type RegistrationFeeBigBrotherDistribution = crate::RegistrationFeeBigBrotherDistribution;
     type RuntimeEvent = crate::RuntimeEvent;
     type RuntimeHoldReason = crate::RuntimeHoldReason;
 }
@@ -23,3 +23,11 @@ impl frame_system::Config for crate::Runtime {
     type SystemWeightInfo = ();
     type Version = crate::Version;
 }
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for crate::Runtime
+where
+    crate::RuntimeCall: From<C>,
+{
+    type Extrinsic = crate::UncheckedExtrinsic;
+    type OverarchingCall = crate::RuntimeCall;
+}